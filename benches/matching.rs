@@ -0,0 +1,57 @@
+//! Throughput benchmark for `Word::try_match`.
+//!
+//! Under `cargo bench` every bundled word is graded
+//! against a few secrets over many rounds and the
+//! throughput is reported. Under `cargo test` a single
+//! round runs as a smoke test.
+
+use std::env;
+use std::time::Instant;
+use wordle::bank::{Dictionary, StaticDict};
+use wordle::game::Word;
+
+/// Rounds over the whole dictionary under `cargo bench`.
+const BENCH_ROUNDS: usize = 50;
+
+const SECRETS: [&str; 4] = ["bathe", "geese", "llama", "crane"];
+
+fn main() {
+    let rounds = if env::args().any(|arg| arg == "--bench") {
+        BENCH_ROUNDS
+    } else {
+        1
+    };
+
+    // try_match takes a String, so the words are
+    // copied once, outside of the timed loop
+    let words: Vec<String> = StaticDict.iter_words()
+        .map(String::from)
+        .collect();
+    let secrets: Vec<Word> = SECRETS.iter()
+        .map(|secret| Word::analyze_str(secret))
+        .collect();
+
+    let start = Instant::now();
+    let mut full_matches = 0;
+    for _ in 0..rounds {
+        for secret in &secrets {
+            for word in &words {
+                if secret.try_match(word).full_match() {
+                    full_matches += 1;
+                }
+            }
+        }
+    }
+    let elapsed = start.elapsed();
+
+    // every secret is in the dictionary exactly once
+    assert_eq!(rounds * secrets.len(), full_matches);
+
+    let grades = rounds * secrets.len() * words.len();
+    println!(
+        "try_match: {} grades in {:?} ({:.0} grades/s)",
+        grades,
+        elapsed,
+        grades as f64 / elapsed.as_secs_f64()
+    );
+}
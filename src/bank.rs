@@ -1,42 +1,598 @@
-use rand::Rng;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use unicode_segmentation::UnicodeSegmentation;
 use crate::game::Word;
 
+/// Letters that count as vowels for [has_min_vowels].
+const VOWELS: [&str; 5] = ["a", "e", "i", "o", "u"];
+
+/// Shortest run of consonants that
+/// [is_pronounceable] finds awkward.
+const CONSONANT_RUN: usize = 4;
+
+/// Seconds in a day, for [daily_seed].
+const DAY_SECONDS: u64 = 24 * 60 * 60;
+
 pub trait Dictionary {
 
-    fn generate(&self) -> Word;
+    /// Picks a random word.
+    fn generate(&self) -> Result<Word, DictError> {
+        self.generate_seeded(rand::random())
+    }
+
+    /// Picks a word deterministically: the
+    /// same seed always picks the same word.
+    fn generate_seeded(&self, seed: u64) -> Result<Word, DictError>;
+
+    /// Iterates over every word in the
+    /// dictionary without copying them.
+    fn iter_words(&self) -> impl Iterator<Item = &str>;
+
+    /// Counts the words in the dictionary.
+    fn len(&self) -> usize {
+        self.iter_words().count()
+    }
+
+    /// Returns `true` if the dictionary has no words.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Lists every word in the dictionary.
+    fn words(&self) -> Vec<String> {
+        self.iter_words()
+            .map(String::from)
+            .collect()
+    }
+
+    /// Checks whether `word` is in the dictionary,
+    /// ignoring case and surrounding whitespace.
+    fn contains(&self, word: &str) -> bool {
+        let word = word.trim().to_lowercase();
+        self.iter_words()
+            .any(|known| known == word)
+    }
+
+    /// Lists the words that differ from `word` in exactly
+    /// one position, with all other letters in place.
+    fn neighbors_of(&self, word: &str) -> Vec<String> {
+        let letters: Vec<&str> = word.graphemes(true).collect();
+        self.iter_words()
+            .filter(|candidate| {
+                let other: Vec<&str> = candidate.graphemes(true).collect();
+                other.len() == letters.len() && other.iter()
+                    .zip(&letters)
+                    .filter(|(a, b)| a != b)
+                    .count() == 1
+            })
+            .map(String::from)
+            .collect()
+    }
+
+    /// Picks a random word out of those that satisfy
+    /// `accept`, skipping malformed entries. Fails with
+    /// [DictError::NoMatch] if no word in the dictionary does.
+    fn generate_where(&self, accept: impl Fn(&Word) -> bool) -> Result<Word, DictError>
+        where Self: Sized {
+        if self.is_empty() {
+            return Err(DictError::EmptyDictionary);
+        }
+
+        // a malformed entry can't be picked anyway, so it
+        // shouldn't keep the well-formed ones from being
+        let candidates: Vec<&str> = self.iter_words()
+            .filter(|word| to_word(word).is_ok_and(|word| accept(&word)))
+            .collect();
+
+        if candidates.is_empty() {
+            return Err(DictError::NoMatch);
+        }
+        pick_seeded(&candidates, rand::random())
+    }
+
+}
+
+/// Picks a word out of `words` by `seed`, the
+/// way a [Dictionary::generate_seeded] would.
+///
+/// The index is derived from the seed by [scramble]
+/// rather than by a `rand` generator, whose output
+/// may change between versions and platforms, so the
+/// daily word is the same for everyone.
+pub(crate) fn pick_seeded(words: &[impl AsRef<str>], seed: u64) -> Result<Word, DictError> {
+    if words.is_empty() {
+        return Err(DictError::EmptyDictionary);
+    }
+
+    let index = scramble(seed) % words.len() as u64;
+    to_word(words[index as usize].as_ref())
+}
+
+/// Spreads consecutive seeds far apart with the
+/// SplitMix64 finalizer, so neighbouring days
+/// don't pick neighbouring words.
+fn scramble(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Turns a dictionary entry into a [Word],
+/// unless it can't be a secret word.
+fn to_word(word: &str) -> Result<Word, DictError> {
+    if word.is_empty() || word.chars().any(char::is_whitespace) {
+        return Err(DictError::MalformedWord(String::from(word)));
+    }
+
+    Ok(Word::analyze_str(word))
+}
+
+/// A custom [Error] type that reports why
+/// a [Dictionary] couldn't pick a word.
+#[derive(Debug, PartialEq)]
+pub enum DictError {
+
+    /// The dictionary has no words to pick from.
+    EmptyDictionary,
+
+    /// The picked entry can't be a secret word.
+    MalformedWord(String),
+
+    /// No word in the dictionary fits the filter
+    /// given to [Dictionary::generate_where].
+    NoMatch
+
+}
+
+impl Display for DictError {
+
+    /// Explains why no word could be picked.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DictError::EmptyDictionary => write!(f, "The dictionary has no words"),
+            DictError::MalformedWord(word) => write!(
+                f,
+                "The dictionary has a malformed word '{}'",
+                word
+            ),
+            DictError::NoMatch => write!(f, "No word in the dictionary fits the chosen filters")
+        }
+    }
+}
+
+impl Error for DictError {}
+
+/// Checks that a word has at least `min` vowels.
+pub fn has_min_vowels(word: &Word, min: usize) -> bool {
+    let vowels: usize = word.letter_counts()
+        .iter()
+        .filter(|(ch, _)| VOWELS.contains(&ch.as_str()))
+        .map(|(_, count)| count)
+        .sum();
+    vowels >= min
+}
+
+/// A rough check that a word is easy to say: it has
+/// a vowel and no run of 4 or more consonants.
+pub fn is_pronounceable(word: &Word) -> bool {
+    let mut run = 0;
+    let mut has_vowel = false;
+
+    for ch in word.reveal().graphemes(true) {
+        if VOWELS.contains(&ch) {
+            has_vowel = true;
+            run = 0;
+        } else {
+            run += 1;
+            if run >= CONSONANT_RUN {
+                return false;
+            }
+        }
+    }
 
+    has_vowel
 }
 
+/// The bundled word list, split in parts.
+const SHARDS: [&str; 6] = [
+    include_str!("../assets/part-1.csv"),
+    include_str!("../assets/part-2.csv"),
+    include_str!("../assets/part-3.csv"),
+    include_str!("../assets/part-4.csv"),
+    include_str!("../assets/part-5.csv"),
+    include_str!("../assets/part-6.csv")
+];
+
+/// The bundled word list out of all [SHARDS],
+/// parsed once on first use.
+fn bundled_words() -> &'static [String] {
+    static WORDS: OnceLock<Vec<String>> = OnceLock::new();
+    WORDS.get_or_init(|| SHARDS.iter()
+        .flat_map(|shard| parse_words(shard))
+        .map(String::from)
+        .collect()
+    )
+}
 
 pub struct StaticDict;
 
 impl Dictionary for StaticDict {
 
-    fn generate(&self) -> Word {
-        let mut rnd = rand::thread_rng();
+    fn generate_seeded(&self, seed: u64) -> Result<Word, DictError> {
+        pick_seeded(bundled_words(), seed)
+    }
+
+    fn iter_words(&self) -> impl Iterator<Item = &str> {
+        bundled_words().iter()
+            .map(String::as_str)
+    }
+
+    fn len(&self) -> usize {
+        bundled_words().len()
+    }
 
-        let file_n = rnd.gen_range(1..7);
+}
 
-        let file_contents = match file_n {
-            1 => include_str!("../assets/part-1.csv"),
-            2 => include_str!("../assets/part-2.csv"),
-            3 => include_str!("../assets/part-3.csv"),
-            4 => include_str!("../assets/part-4.csv"),
-            5 => include_str!("../assets/part-5.csv"),
-            6 => include_str!("../assets/part-6.csv"),
-            _ => panic!("Weird file index")
-        };
+/// A dictionary loaded from a
+/// user-supplied word list file.
+pub struct FileDict {
+    words: Vec<String>
+}
 
-        let lines: Vec<&str> = file_contents
-            .split('\n')
-            .collect();
+impl FileDict {
 
-        let line_n = rnd.gen_range(0..lines.len());
-        let word = lines.get(line_n)
-            .expect("Failed to get word");
+    /// Loads newline-separated words from `path`.
+    /// Lines that aren't five lowercase letters
+    /// are skipped with a warning.
+    pub fn open(path: &Path) -> Result<FileDict, LoadError> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| LoadError::Unreadable(path.to_path_buf(), e))?;
 
-        Word::analyze_str(word)
+        let mut words = Vec::new();
+        for word in parse_words(&contents) {
+            if is_valid_word(word) {
+                words.push(String::from(word));
+            } else {
+                eprintln!(
+                    "Skipping '{}' in {}: not a 5-letter lowercase word",
+                    word,
+                    path.display()
+                );
+            }
+        }
+
+        if words.is_empty() {
+            return Err(LoadError::Empty(path.to_path_buf()));
+        }
+
+        Ok(FileDict { words })
     }
 
 }
 
+impl Dictionary for FileDict {
+
+    fn generate_seeded(&self, seed: u64) -> Result<Word, DictError> {
+        pick_seeded(&self.words, seed)
+    }
+
+    fn iter_words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter()
+            .map(String::as_str)
+    }
+
+    fn len(&self) -> usize {
+        self.words.len()
+    }
+
+}
+
+/// Checks that a word list entry is
+/// made of five lowercase letters.
+fn is_valid_word(word: &str) -> bool {
+    word.graphemes(true).count() == 5 && word.graphemes(true)
+        .all(|letter| letter.chars().next().is_some_and(char::is_lowercase))
+}
+
+/// A custom [Error] type that reports
+/// why a [FileDict] couldn't be loaded.
+#[derive(Debug)]
+pub enum LoadError {
+
+    /// The file couldn't be read.
+    Unreadable(PathBuf, io::Error),
+
+    /// The file has no valid words.
+    Empty(PathBuf)
+
+}
+
+impl Display for LoadError {
+
+    /// Explains what is wrong with the word list.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Unreadable(path, e) => write!(
+                f,
+                "Couldn't read word list {}: {}",
+                path.display(),
+                e
+            ),
+            LoadError::Empty(path) => write!(
+                f,
+                "Word list {} has no 5-letter words",
+                path.display()
+            )
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+/// Splits a word list into words, trimming
+/// stray whitespace such as the `\r` of Windows
+/// line endings and skipping blank lines.
+fn parse_words(file_contents: &str) -> impl Iterator<Item = &str> {
+    file_contents
+        .split('\n')
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+}
+
+/// Derives the seed of the day `now` falls on (in UTC), so
+/// [Dictionary::generate_seeded] picks everyone the same
+/// word on the same day.
+pub fn daily_seed(now: SystemTime) -> u64 {
+    let since_epoch = now.duration_since(UNIX_EPOCH)
+        .expect("System clock is before the UNIX epoch");
+    since_epoch.as_secs() / DAY_SECONDS
+}
+
+/// Dictionaries for tests across the crate.
+#[cfg(test)]
+pub(crate) mod fixture {
+    use crate::bank::{DictError, Dictionary, pick_seeded};
+    use crate::game::Word;
+
+    /// A tiny [Dictionary] over a fixed word list.
+    pub(crate) struct ListDict(pub(crate) Vec<&'static str>);
+
+    impl Dictionary for ListDict {
+        fn generate_seeded(&self, seed: u64) -> Result<Word, DictError> {
+            pick_seeded(&self.0, seed)
+        }
+
+        fn iter_words(&self) -> impl Iterator<Item = &str> {
+            self.0.iter().copied()
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process;
+    use std::time::{Duration, UNIX_EPOCH};
+    use crate::bank::{DAY_SECONDS, DictError, Dictionary, FileDict, LoadError, StaticDict,
+                      bundled_words, daily_seed, has_min_vowels, is_pronounceable, parse_words,
+                      scramble};
+    use crate::bank::fixture::ListDict;
+    use crate::game::Word;
+
+    #[test]
+    fn parse_words_strips_carriage_returns_and_blanks() {
+        let words: Vec<&str> = parse_words("bathe\r\ncrane\r\n\r\n  \r\nlathe\r\n")
+            .collect();
+        assert_eq!(vec!["bathe", "crane", "lathe"], words);
+    }
+
+    #[test]
+    fn bundled_words_are_five_letters() {
+        let words = bundled_words();
+
+        assert!(!words.is_empty());
+        assert!(words.iter().all(|word| word.chars().count() == 5));
+        assert_eq!(words.len(), StaticDict.len());
+    }
+
+    #[test]
+    fn generate_seeded_same_seed_same_word() {
+        for seed in [0, 42, u64::MAX] {
+            let first = StaticDict.generate_seeded(seed).unwrap();
+            let second = StaticDict.generate_seeded(seed).unwrap();
+            assert_eq!(first.reveal(), second.reveal());
+        }
+    }
+
+    #[test]
+    fn generate_seeded_golden_words() {
+        // pins the picks, so a change in how seeds
+        // pick words doesn't go by unnoticed
+        assert_eq!(0xe220_a839_7b1d_cdaf, scramble(0));
+        assert_eq!("pownd", StaticDict.generate_seeded(0).unwrap().reveal());
+        assert_eq!("mutha", StaticDict.generate_seeded(19_000).unwrap().reveal());
+        assert_eq!("purer", StaticDict.generate_seeded(u64::MAX).unwrap().reveal());
+    }
+
+    #[test]
+    fn daily_seed_changes_once_a_day() {
+        let morning = UNIX_EPOCH + Duration::from_secs(19_000 * DAY_SECONDS + 60);
+        let evening = morning + Duration::from_secs(12 * 60 * 60);
+        let next_day = morning + Duration::from_secs(DAY_SECONDS);
+
+        assert_eq!(19_000, daily_seed(morning));
+        assert_eq!(daily_seed(morning), daily_seed(evening));
+        assert_eq!(daily_seed(morning) + 1, daily_seed(next_day));
+    }
+
+    /// Writes `contents` to a file in the temp dir
+    /// named after the test, so tests don't clash.
+    fn temp_word_list(name: &str, contents: &str) -> PathBuf {
+        let path = env::temp_dir()
+            .join(format!("wordle-{}-{}.txt", name, process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn file_dict_skips_invalid_lines() {
+        let path = temp_word_list(
+            "mixed",
+            "bathe\nCRANE\nbank\nlathes\nb4the\n\ncrêpe\r\nbraid\n"
+        );
+        let dict = FileDict::open(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(vec!["bathe", "crêpe", "braid"], dict.words());
+        for seed in 0..20 {
+            assert!(dict.contains(dict.generate_seeded(seed).unwrap().reveal()));
+        }
+    }
+
+    #[test]
+    fn file_dict_without_valid_words_is_empty() {
+        let path = temp_word_list("empty", "BATHE\nbank\n");
+        let result = FileDict::open(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(LoadError::Empty(_))));
+    }
+
+    #[test]
+    fn file_dict_missing_file_is_unreadable() {
+        let path = env::temp_dir().join("wordle-does-not-exist.txt");
+        assert!(matches!(FileDict::open(&path), Err(LoadError::Unreadable(_, _))));
+    }
+
+    #[test]
+    fn static_dict_words_spans_all_shards() {
+        let words = StaticDict.words();
+
+        assert!(words.contains(&String::from("aahed")));
+        assert!(words.contains(&String::from("zymic")));
+    }
+
+    #[test]
+    fn static_dict_iter_words_matches_words() {
+        assert_eq!(StaticDict.words().len(), StaticDict.iter_words().count());
+    }
+
+    #[test]
+    fn static_dict_contains_known_word() {
+        assert!(StaticDict.contains("bathe"));
+        assert!(StaticDict.contains(" BaThE\n"));
+    }
+
+    #[test]
+    fn static_dict_does_not_contain_gibberish() {
+        assert!(!StaticDict.contains("xqzvj"));
+        assert!(!StaticDict.contains("aaaaa"));
+    }
+
+    #[test]
+    fn neighbors_of_differ_in_one_position() {
+        let dict = ListDict(vec!["bathe", "lathe", "baths", "bathe", "crane", "blthr", "abthe"]);
+
+        assert_eq!(
+            vec![String::from("lathe"), String::from("baths")],
+            dict.neighbors_of("bathe")
+        );
+    }
+
+    #[test]
+    fn has_min_vowels_counts_repeated_vowels() {
+        assert!(has_min_vowels(&Word::analyze_str("geese"), 3));
+        assert!(!has_min_vowels(&Word::analyze_str("crwth"), 1));
+    }
+
+    #[test]
+    fn generate_where_meets_vowel_minimum() {
+        let dict = StaticDict;
+
+        for _ in 0..20 {
+            let word = dict.generate_where(|word| has_min_vowels(word, 2)).unwrap();
+            assert!(has_min_vowels(&word, 2));
+        }
+    }
+
+    #[test]
+    fn is_pronounceable_heuristic() {
+        assert!(is_pronounceable(&Word::analyze_str("bathe")));
+        assert!(is_pronounceable(&Word::analyze_str("plant")));
+        assert!(!is_pronounceable(&Word::analyze_str("angst")));
+        assert!(!is_pronounceable(&Word::analyze_str("crwth")));
+    }
+
+    #[test]
+    fn generate_where_pronounceable() {
+        let dict = StaticDict;
+
+        for _ in 0..20 {
+            let word = dict.generate_where(is_pronounceable).unwrap();
+            assert!(is_pronounceable(&word));
+        }
+    }
+
+    #[test]
+    fn generate_where_finds_rare_words() {
+        // only a few dozen bundled words have 4 vowels
+        for _ in 0..3 {
+            let word = StaticDict.generate_where(|word| has_min_vowels(word, 4)).unwrap();
+            assert!(has_min_vowels(&word, 4));
+        }
+    }
+
+    #[test]
+    fn generate_where_single_candidate() {
+        let dict = ListDict(vec!["crwth", "audio", "psych"]);
+
+        for _ in 0..10 {
+            let word = dict.generate_where(|word| has_min_vowels(word, 2)).unwrap();
+            assert_eq!("audio", word.reveal());
+        }
+    }
+
+    #[test]
+    fn generate_where_skips_malformed_words() {
+        let dict = ListDict(vec!["ba he", "audio", ""]);
+
+        let word = dict.generate_where(|word| has_min_vowels(word, 2)).unwrap();
+        assert_eq!("audio", word.reveal());
+    }
+
+    #[test]
+    fn generate_where_no_word_qualifies() {
+        let dict = ListDict(vec!["crwth", "psych", "lynch"]);
+        assert_eq!(
+            Some(DictError::NoMatch),
+            dict.generate_where(|word| has_min_vowels(word, 2)).err()
+        );
+    }
+
+    #[test]
+    fn generate_from_empty_dictionary_fails() {
+        let dict = ListDict(vec![]);
+
+        assert_eq!(Some(DictError::EmptyDictionary), dict.generate().err());
+        assert_eq!(Some(DictError::EmptyDictionary), dict.generate_where(|_| true).err());
+    }
+
+    #[test]
+    fn generate_malformed_word_fails() {
+        let dict = ListDict(vec!["ba he"]);
+
+        assert_eq!(
+            Some(DictError::MalformedWord(String::from("ba he"))),
+            dict.generate().err()
+        );
+    }
+}
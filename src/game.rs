@@ -1,63 +1,530 @@
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
-use std::io::stdin;
-use std::iter::{Enumerate, repeat};
-use std::ops::Add;
-use std::str::Chars;
+use std::io::{BufRead, ErrorKind, Write, stdin, stdout};
+use std::iter::{Enumerate, repeat_n};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use colored::{ColoredString, Colorize};
+use unicode_segmentation::{Graphemes, UnicodeSegmentation};
+use crate::bank::Dictionary;
 
-/// Amount of attempts a user has
-/// to guess the secret word.
-const ATTEMPT_COUNT: u8 = 6;
+/// Default amount of attempts a user
+/// has to guess the secret word.
+pub const ATTEMPT_COUNT: u8 = 6;
 
-/// Game result: an empty tuple if
-/// the game loop terminated with
-/// a correct guess of a [Word];
-/// a [GameLost] error - if otherwise.
-pub type Result = std::result::Result<(), GameLost>;
+/// How a finished game went.
+#[derive(Debug)]
+pub struct Outcome {
+
+    /// Amount of guesses played, counting the
+    /// winning one; unbounded in [GameConfig::practice].
+    pub attempts: u32,
+
+    /// The result of every guess, in order.
+    pub guesses: Vec<GuessResult>,
+
+    /// Why the game was lost, or `None`
+    /// if the [Word] was guessed.
+    pub lost: Option<GameLost>,
+
+    /// Wall clock time from the start
+    /// of the game to its end.
+    pub elapsed: Duration
+
+}
+
+impl Outcome {
+
+    /// Returns `true` if the game ended
+    /// with a correct guess.
+    pub fn won(&self) -> bool {
+        self.lost.is_none()
+    }
+
+}
+
+/// Player-selected options that
+/// tweak how the game loop behaves.
+#[derive(Debug)]
+pub struct GameConfig {
+
+    /// Amount of attempts to guess the
+    /// secret, [ATTEMPT_COUNT] by default.
+    pub attempts: u8,
+
+    /// Prints the attempts left as a
+    /// row of dots after every guess.
+    pub show_remaining: bool,
+
+    /// Warns when a guess moves away from
+    /// a letter already known to be green.
+    pub warn_known_greens: bool,
+
+    /// Grants one extra attempt if the last
+    /// allowed guess missed by a single letter.
+    /// Ignored in [GameConfig::blind] mode, as
+    /// granting it would give the result away.
+    pub grace_attempt: bool,
+
+    /// Never prints the secret word, so
+    /// it can't be spoiled on screen.
+    pub hide_secret: bool,
+
+    /// Drops punctuation trailing a guess,
+    /// as often pasted in from a chat.
+    pub strip_punctuation: bool,
+
+    /// A word played automatically as the
+    /// first guess, counting as an attempt.
+    pub fixed_opener: Option<String>,
+
+    /// Describes each result in words instead
+    /// of printing a colored row, for screen readers.
+    pub describe_results: bool,
+
+    /// Least amount of vowels the secret
+    /// word must have; `0` allows any word.
+    pub min_vowels: usize,
+
+    /// Only picks secrets that pass
+    /// the [crate::bank::is_pronounceable] check.
+    pub pronounceable: bool,
+
+    /// Plays the word of the day, the
+    /// same for everyone on that day.
+    pub daily: bool,
+
+    /// A file to load the words from instead
+    /// of the bundled dictionary.
+    pub word_list: Option<PathBuf>,
+
+    /// Prints the typed guess on its own
+    /// line above its result.
+    pub echo_guess: bool,
+
+    /// Holds back all results until the game
+    /// is over, then shows the whole board.
+    pub blind: bool,
+
+    /// Shows how long each guess took
+    /// to type next to its result.
+    pub show_timing: bool,
+
+    /// Marks every letter with a symbol as well as
+    /// a color, for players who can't tell the
+    /// colors apart: `[x]`, `(x)` and ` x `.
+    pub symbols: bool,
+
+    /// Lets the player keep guessing until the word
+    /// is found, ignoring [GameConfig::attempts].
+    pub practice: bool,
+
+    /// Requires every guess to keep green letters
+    /// in place and reuse every revealed letter.
+    /// Ignored in [GameConfig::blind] mode, as its
+    /// complaints would give the hints away.
+    pub hard_mode: bool,
+
+    /// Tells which guess won the game as an
+    /// ordinal, e.g. `Solved on the 3rd guess`.
+    pub ordinal_count: bool
+
+}
+
+impl Default for GameConfig {
+    fn default() -> GameConfig {
+        GameConfig {
+            attempts: ATTEMPT_COUNT,
+            show_remaining: false,
+            warn_known_greens: false,
+            grace_attempt: false,
+            hide_secret: false,
+            strip_punctuation: false,
+            fixed_opener: None,
+            describe_results: false,
+            min_vowels: 0,
+            pronounceable: false,
+            daily: false,
+            word_list: None,
+            echo_guess: false,
+            blind: false,
+            show_timing: false,
+            symbols: false,
+            practice: false,
+            hard_mode: false,
+            ordinal_count: false
+        }
+    }
+}
 
 /// Runs the game loop until
 /// either the [Word] is guessed
 /// or the number of attempts
-/// reaches [ATTEMPT_COUNT].
-pub fn start_game_loop(word: &Word) -> Result {
+/// reaches [GameConfig::attempts],
+/// unless in [GameConfig::practice].
+pub fn start_game_loop(word: &Word, dict: &impl Dictionary, config: &GameConfig) -> Outcome {
+    run_game_loop(word, dict, config, &mut stdin().lock(), &mut stdout())
+}
 
+/// Same as [start_game_loop], but reads guesses
+/// from `input` and writes everything to `output`.
+///
+/// Reaching the end of `input` ends the game
+/// as lost, since no more guesses can come.
+fn run_game_loop(word: &Word, dict: &impl Dictionary, config: &GameConfig,
+                 input: &mut impl BufRead, output: &mut impl Write) -> Outcome {
+    run_timed_game_loop(word, dict, config, input, output, &mut Instant::now)
+}
+
+/// Same as [run_game_loop], but times how long each
+/// guess takes to type by reading `clock` before and
+/// after it, for [GameConfig::show_timing].
+fn run_timed_game_loop(word: &Word, dict: &impl Dictionary, config: &GameConfig,
+                       input: &mut impl BufRead, output: &mut impl Write,
+                       clock: &mut impl FnMut() -> Instant) -> Outcome {
+
+    let began = Instant::now();
     let mut attempt_n = 0;
+    let mut attempt_limit = config.attempts;
+    let mut history: Vec<(String, GuessResult)> = Vec::new();
+    let mut opener = config.fixed_opener.clone();
+    let mut keyboard = Keyboard::default();
+    let mut guessed: HashSet<String> = HashSet::new();
     loop {
 
-        if attempt_n == ATTEMPT_COUNT {
-            return Result::Err(GameLost::with_word(word));
+        if !config.practice && attempt_n == u32::from(attempt_limit) {
+            let near_miss = history.last()
+                .map(|(_, result)| result.near_miss())
+                .unwrap_or(false);
+            // at the largest limit there is no room for
+            // one more attempt, so no grace is granted
+            match attempt_limit.checked_add(1) {
+                Some(extended) if config.grace_attempt && !config.blind
+                    && attempt_limit == config.attempts && near_miss => {
+                    say(output, "So close! Have one more go");
+                    attempt_limit = extended;
+                },
+                _ => {
+                    let lost = GameLost::with_word(word, !config.hide_secret);
+                    return game_over(lost, config, attempt_n, history, began, output);
+                }
+            }
+        }
+
+        let mut line = String::new();
+        let mut elapsed = Duration::ZERO;
+        if let Some(first) = opener.take() {
+            say(output, format!("Opening with '{}'", first));
+            line = first;
+        } else {
+            let started = clock();
+            let read = input.read_line(&mut line);
+            elapsed = clock().saturating_duration_since(started);
+            match read {
+                Ok(0) => {
+                    let lost = GameLost::with_word(word, !config.hide_secret);
+                    return game_over(lost, config, attempt_n, history, began, output);
+                },
+                Ok(_) => {},
+                Err(e) if e.kind() == ErrorKind::InvalidData => {
+                    say(output, "Couldn't read that - try again");
+                    continue;
+                },
+                Err(e) => panic!("Failed to read user input: {}", e)
+            }
+        }
+
+        let mut guess = line.trim();
+
+        if let Some(Command::GiveUp) = parse_command(guess) {
+            let lost = GameLost::given_up(word, !config.hide_secret);
+            return game_over(lost, config, attempt_n, history, began, output);
+        }
+
+        if config.strip_punctuation {
+            guess = strip_trailing_punctuation(guess);
+        }
+
+        // counts letters rather than bytes, but stops
+        // right after one too many, so a huge pasted line
+        // is rejected before anything copies it
+        if guess.graphemes(true).take(word.len() + 1).count() != word.len() {
+            say(output, format!("You'll need {} characters to make it work!", word.len()));
+            continue;
+        }
+
+        if !dict.contains(guess) {
+            say(output, "Not in word list");
+            continue;
         }
+        let guess = normalize_guess(guess);
 
-        let mut input = String::new();
-        stdin()
-            .read_line(&mut input)
-            .expect("Failed to read user input");
+        if config.echo_guess {
+            say(output, &guess);
+        }
 
-        let guess = String::from(input.trim_end());
+        if config.hard_mode && !config.blind {
+            if let Some(violation) = hard_mode_violation(&guess, &history) {
+                say(output, violation);
+                continue;
+            }
+        }
 
-        if guess.len() != 5 {
-            println!("You'll need 5 characters to make it work!");
+        if is_repeat_guess(&mut guessed, &guess) {
+            say(output, "You already guessed that");
             continue;
         }
 
+        if config.warn_known_greens && !config.blind {
+            for (ind, ch) in contradicted_greens(&guess, &history) {
+                say(output, format!(
+                    "Position {} is known to be {}",
+                    ind + 1,
+                    ch.to_uppercase()
+                ));
+            }
+        }
+
         let result = word.try_match(&guess);
 
         if result.full_match() {
-            println!(
-                "{} {}",
+            history.push((guess, result));
+            if config.blind {
+                reveal_board(config, &history, output);
+            }
+            let attempts = attempt_n + 1;
+            let elapsed = began.elapsed();
+            let solved = if config.ordinal_count {
+                solved_on(attempts)
+            } else {
+                solved_in(attempts)
+            };
+            say(output, format!(
+                "{} {} in {:.1}s",
                 "You won!".green(),
-                format!("You needed {} attempts", attempt_n).normal()
-            );
-            return Result::Ok(());
+                solved.normal(),
+                elapsed.as_secs_f64()
+            ));
+            return Outcome {
+                attempts,
+                guesses: results_of(history),
+                lost: None,
+                elapsed
+            };
         } else {
-            result.print_result_for(&guess);
-            attempt_n = attempt_n.add(1);
+            keyboard.record(&guess, &result);
+            if !config.blind {
+                let elapsed = config.show_timing.then_some(elapsed);
+                show_result(config, &guess, &result, elapsed, output);
+                // colors alone would read out as a bare alphabet
+                if !config.describe_results {
+                    say(output, keyboard.render(config.symbols));
+                }
+            }
+            attempt_n += 1;
+            if config.show_remaining && !config.practice {
+                say(output, remaining_attempts(attempt_n, u32::from(attempt_limit)));
+            }
+            history.push((guess, result));
+        }
+
+    }
+
+}
+
+/// Brings a guess into the form the secret
+/// is kept in: trimmed and lowercase.
+fn normalize_guess(guess: &str) -> String {
+    guess.trim().to_lowercase()
+}
+
+/// Remembers `guess` in `guessed` and tells
+/// whether it had already been guessed.
+fn is_repeat_guess(guessed: &mut HashSet<String>, guess: &str) -> bool {
+    !guessed.insert(String::from(guess))
+}
+
+/// Punctuation dropped from the end of a guess
+/// if [GameConfig::strip_punctuation] is set.
+const TRAILING_PUNCTUATION: [char; 6] = ['.', ',', '!', '?', '"', '\''];
+
+/// Removes any [TRAILING_PUNCTUATION] from the end of a guess.
+fn strip_trailing_punctuation(guess: &str) -> &str {
+    guess.trim_end_matches(TRAILING_PUNCTUATION)
+}
+
+/// Lists the positions (with the pinned letter)
+/// where `guess` drops a letter that an earlier
+/// guess in `history` already got green.
+fn contradicted_greens<'h>(guess: &str,
+                           history: &'h [(String, GuessResult)]) -> Vec<(usize, &'h str)> {
+    let guessed: Vec<&str> = guess.graphemes(true).collect();
+    let mut contradicted: Vec<(usize, &str)> = Vec::new();
+
+    for (word, result) in history {
+        for (ind, (ch, res)) in word.graphemes(true).zip(&result.result).enumerate() {
+            let pinned = *res == MatchResult::Match;
+            if pinned && guessed.get(ind) != Some(&ch) && !contradicted.contains(&(ind, ch)) {
+                contradicted.push((ind, ch));
+            }
+        }
+    }
+
+    contradicted.sort_unstable();
+    contradicted
+}
+
+/// Checks `guess` against the hints revealed by the
+/// guesses in `history`, as hard mode demands: every
+/// green letter stays in place and every revealed
+/// letter is reused at least as many times as it has
+/// been found. Explains the first broken rule, if any.
+fn hard_mode_violation(guess: &str, history: &[(String, GuessResult)]) -> Option<String> {
+    if let Some((ind, ch)) = contradicted_greens(guess, history).first() {
+        return Some(format!("Must use {} in position {}", ch.to_uppercase(), ind + 1));
+    }
+
+    let mut required: Vec<(&str, usize)> = Vec::new();
+    for (word, result) in history {
+        let mut found: HashMap<&str, usize> = HashMap::new();
+        for (ch, res) in word.graphemes(true).zip(&result.result) {
+            if *res != MatchResult::None {
+                *found.entry(ch).or_default() += 1;
+            }
         }
+        for (ch, count) in found {
+            match required.iter_mut().find(|(known, _)| *known == ch) {
+                Some((_, most)) => *most = (*most).max(count),
+                None => required.push((ch, count))
+            }
+        }
+    }
+    required.sort_unstable();
+
+    required.into_iter()
+        .find(|(ch, count)| guess.graphemes(true).filter(|other| other == ch).count() < *count)
+        .map(|(ch, count)| if count == 1 {
+            format!("Guess must contain {}", ch.to_uppercase())
+        } else {
+            format!("Guess must contain {} {} times", ch.to_uppercase(), count)
+        })
+}
+
+/// Spells out a 1-based position, e.g. `1st` or `12th`.
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th"
+    };
+    format!("{}{}", n, suffix)
+}
+
+/// Writes a line of game output.
+fn say(output: &mut impl Write, line: impl Display) {
+    writeln!(output, "{}", line)
+        .expect("Failed to write game output");
+}
+
+/// Ends a game as `lost`, showing the board
+/// first if the results were held back.
+fn game_over(lost: GameLost, config: &GameConfig, attempts: u32,
+             history: Vec<(String, GuessResult)>, began: Instant,
+             output: &mut impl Write) -> Outcome {
+    if config.blind {
+        reveal_board(config, &history, output);
+    }
+    Outcome {
+        attempts,
+        guesses: results_of(history),
+        lost: Some(lost),
+        elapsed: began.elapsed()
+    }
+}
+
+/// Commands a player can type in
+/// place of a guess.
+#[derive(Debug, PartialEq)]
+enum Command {
+
+    /// Ends the game and reveals the word.
+    GiveUp
+
+}
+
+/// Recognizes a [Command] in a line of input.
+fn parse_command(line: &str) -> Option<Command> {
+    match line.trim() {
+        ":giveup" => Some(Command::GiveUp),
+        _ => None
+    }
+}
+
+/// Drops the guessed words from a history,
+/// keeping only their results.
+fn results_of(history: Vec<(String, GuessResult)>) -> Vec<GuessResult> {
+    history.into_iter()
+        .map(|(_, result)| result)
+        .collect()
+}
+
+/// Prints every guess in `history` with its result.
+fn reveal_board(config: &GameConfig, history: &[(String, GuessResult)], output: &mut impl Write) {
+    say(output, "Your board:");
+    for (guess, result) in history {
+        show_result(config, guess, result, None, output);
+    }
+}
+
+/// Prints the result of a guess, either as a colored
+/// row or described in words, followed by the time
+/// the guess took if there is one.
+fn show_result(config: &GameConfig, guess: &str, result: &GuessResult,
+               elapsed: Option<Duration>, output: &mut impl Write) {
+    let line = if config.describe_results {
+        result.describe(guess)
+    } else {
+        result.render_for(guess, config.symbols)
+    };
+
+    match elapsed {
+        Some(elapsed) => say(output, with_elapsed(line, elapsed)),
+        None => say(output, line)
+    }
+}
+
+/// Annotates a result line with the
+/// time taken, e.g. `b r a i d  3.2s`.
+fn with_elapsed(line: String, elapsed: Duration) -> String {
+    format!("{}  {:.1}s", line, elapsed.as_secs_f64())
+}
 
+/// Describes how many guesses it took to win,
+/// counting the winning guess itself.
+fn solved_in(guesses: u32) -> String {
+    if guesses == 1 {
+        String::from("Solved in 1 guess")
+    } else {
+        format!("Solved in {} guesses", guesses)
     }
+}
 
+/// Tells which guess won the game, as an
+/// ordinal, e.g. `Solved on the 3rd guess`.
+fn solved_on(guesses: u32) -> String {
+    format!("Solved on the {} guess", ordinal(guesses as usize))
+}
+
+/// Renders the attempts left as filled dots
+/// followed by the used ones as hollow dots.
+fn remaining_attempts(used: u32, total: u32) -> String {
+    let left = total.saturating_sub(used) as usize;
+    let mut dots = "●".repeat(left);
+    dots.push_str(&"○".repeat(total as usize - left));
+    dots
 }
 
 
@@ -65,16 +532,34 @@ pub fn start_game_loop(word: &Word) -> Result {
 /// that a game is lost.
 #[derive(Debug)]
 pub struct GameLost {
-    secret: String
+    secret: Option<String>,
+    gave_up: bool
 }
 
 impl GameLost {
-    /// Constructs a new error object from some secret.
-    fn with_word(word: &Word) -> GameLost {
+    /// Constructs a new error object from some secret,
+    /// which is only kept if it is to be revealed.
+    pub(crate) fn with_word(word: &Word, reveal: bool) -> GameLost {
+        GameLost {
+            secret: reveal.then(|| word.reveal().clone()),
+            gave_up: false
+        }
+    }
+
+    /// Constructs a new error object for a game the player
+    /// gave up on, keeping the secret only if it is to be revealed.
+    fn given_up(word: &Word, reveal: bool) -> GameLost {
         GameLost {
-            secret: word.reveal().clone()
+            secret: reveal.then(|| word.reveal().clone()),
+            gave_up: true
         }
     }
+
+    /// Returns `true` if the player gave up
+    /// rather than ran out of attempts.
+    pub fn gave_up(&self) -> bool {
+        self.gave_up
+    }
 }
 
 impl Display for GameLost {
@@ -82,12 +567,21 @@ impl Display for GameLost {
     /// Prints out the message telling
     /// that a game is lost :(.
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let str = format!(
-            "{} The word was '{}'",
-            "You lost :(".red(),
-            &self.secret
-        );
-        write!(f, "{}", str)
+        let headline = if self.gave_up {
+            "You gave up :(".red()
+        } else {
+            "You lost :(".red()
+        };
+
+        match &self.secret {
+            Some(secret) => write!(
+                f,
+                "{} The word was '{}'",
+                headline,
+                secret
+            ),
+            None => write!(f, "{}", headline)
+        }
     }
 }
 
@@ -102,7 +596,7 @@ impl Error for GameLost {}
 pub struct Word {
 
     val: String,
-    letters: HashMap<char, HashSet<u8>>
+    letters: HashMap<String, HashSet<usize>>
 
 }
 
@@ -115,28 +609,26 @@ impl Word {
 
     /// Constructs a [Word] object out of a string.
     ///
+    /// The word can be of any length; guesses are
+    /// then expected to be of the same length. A
+    /// letter is a grapheme cluster, so an accented
+    /// letter is one letter whether it is written
+    /// precomposed or with a combining accent.
+    ///
     /// # Panics
     /// * Will `panic!` if the string a [Word] is
-    ///   supposed to be constructed from is not
-    ///   precisely 5 characters long.
+    ///   supposed to be constructed from is empty.
     pub fn analyze(word: String) -> Word {
-        if word.len() != 5 {
-            panic!("Secret word must be exactly 5 chars long, got {}", word)
+        if word.is_empty() {
+            panic!("Secret word must not be empty")
         }
 
-        let mut mm: HashMap<char, HashSet<u8>> = HashMap::new();
+        let mut mm: HashMap<String, HashSet<usize>> = HashMap::new();
 
-        for (ind, ch) in word.chars().enumerate() {
-            if mm.contains_key(&ch) {
-                let hset = mm
-                    .get_mut(&ch)
-                    .unwrap();
-                hset.insert(ind as u8);
-            } else {
-                let mut hset = HashSet::new();
-                hset.insert(ind as u8);
-                mm.insert(ch, hset);
-            }
+        for (ind, ch) in word.graphemes(true).enumerate() {
+            mm.entry(String::from(ch))
+                .or_default()
+                .insert(ind);
         }
 
         Word {
@@ -148,38 +640,93 @@ impl Word {
     /// Takes a string and checks it letter-by-letter
     /// against the internally contained secret, thus
     /// producing a [GuessResult].
+    ///
+    /// A letter is only marked as [MatchResult::Exists]
+    /// as many times as the secret has it spare: exact
+    /// matches claim their letters first, and surplus
+    /// occurrences of a letter are [MatchResult::None].
     pub fn try_match(&self, word: &String) -> GuessResult {
         if self.val.eq(word) {
-            return GuessResult::new_all_green();
+            return GuessResult::new_all_green(self.len());
+        }
+
+        let exact: Vec<bool> = word.graphemes(true)
+            .enumerate()
+            .map(|(ind, char)| self.letters
+                .get(char)
+                .map(|hset| hset.contains(&ind))
+                .unwrap_or(false)
+            )
+            .collect();
+
+        let mut unused = self.letter_counts();
+        for (char, _) in word.graphemes(true).zip(&exact).filter(|(_, exact)| **exact) {
+            if let Some(count) = unused.get_mut(char) {
+                *count -= 1;
+            }
         }
 
-        let mut guess = GuessResult::new_empty();
+        let mut guess = GuessResult::new_empty(self.len());
 
-        for (ind, char) in word.chars().enumerate() {
-            let match_result = self.letters
-                .get(&char)
-                .map(|hset| hset.contains(&(ind as u8)))
-                .map(|rs|
-                    if rs {
-                        MatchResult::Match
-                    } else {
+        for (char, exact) in word.graphemes(true).zip(exact) {
+            let match_result = if exact {
+                MatchResult::Match
+            } else {
+                match unused.get_mut(char) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
                         MatchResult::Exists
-                    }
-                )
-                .unwrap_or(MatchResult::None);
+                    },
+                    _ => MatchResult::None
+                }
+            };
             guess.push(match_result);
         }
 
         guess
     }
 
+    /// Tells whether this word could be the secret, given
+    /// that `guess` was graded as `result`: it is as long
+    /// as it takes grading `guess` against this word to
+    /// come out the same, duplicate letters included.
+    pub fn is_consistent_with(&self, guess: &str, result: &GuessResult) -> bool {
+        self.try_match(&String::from(guess)).result == result.result
+    }
+
+    /// Counts how many times each letter occurs in the word.
+    pub fn letter_counts(&self) -> HashMap<String, usize> {
+        self.letters.iter()
+            .map(|(ch, positions)| (ch.clone(), positions.len()))
+            .collect()
+    }
+
+    /// Counts the letters (grapheme clusters,
+    /// not bytes or chars) in the word.
+    // a word is never empty, so there's no is_empty
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.val.graphemes(true).count()
+    }
+
     /// Shows the secret word.
-    fn reveal(&self) -> &String {
+    pub(crate) fn reveal(&self) -> &String {
         &self.val
     }
 
 }
 
+/// Grades `guess` against `secret` in one go, without
+/// a game loop; same as [Word::try_match], duplicate
+/// letters included.
+///
+/// # Panics
+/// * Will `panic!` if `secret` is empty, see [Word::analyze].
+pub fn grade(secret: &str, guess: &str) -> GuessResult {
+    Word::analyze_str(secret)
+        .try_match(&String::from(guess))
+}
+
 /// An in-loop stateful object that
 /// tracks letter matches.
 #[derive(Debug)]
@@ -198,51 +745,129 @@ impl GuessResult {
         *res
     }
 
-    /// Pretty-prints the result of a guess attempt.
-    pub fn print_result_for(&self, word: &String) {
-        let mut chars = word.chars().enumerate();
+    /// Iterates over the [MatchResult]
+    /// of every letter, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &MatchResult> {
+        self.result.iter()
+    }
 
-        println!(
-            "{} {} {} {} {}",
-            GuessResult::get_and_colorize(&mut chars, &self),
-            GuessResult::get_and_colorize(&mut chars, &self),
-            GuessResult::get_and_colorize(&mut chars, &self),
-            GuessResult::get_and_colorize(&mut chars, &self),
-            GuessResult::get_and_colorize(&mut chars, &self)
-        );
+    /// Returns `true` if all letters but
+    /// one have been guessed correctly.
+    fn near_miss(&self) -> bool {
+        let misses = self.result.iter()
+            .filter(|item| **item != MatchResult::Match)
+            .count();
+        misses == 1
+    }
+
+    /// Describes the result of a guess attempt in
+    /// plain words, letter by letter, e.g.
+    /// `1st letter B correct, 2nd letter R not in word`.
+    pub fn describe(&self, word: &str) -> String {
+        let letters: Vec<String> = word.graphemes(true)
+            .zip(&self.result)
+            .enumerate()
+            .map(|(ind, (ch, res))| {
+                let verdict = match res {
+                    MatchResult::Match => "correct",
+                    MatchResult::Exists => "in word but elsewhere",
+                    MatchResult::None => "not in word"
+                };
+                format!("{} letter {} {}", ordinal(ind + 1), ch.to_uppercase(), verdict)
+            })
+            .collect();
+        letters.join(", ")
+    }
+
+    /// Spells the result out in a compact, colorless
+    /// form: `G` for [MatchResult::Match], `Y` for
+    /// [MatchResult::Exists] and `X` for
+    /// [MatchResult::None], e.g. `GXYXX`.
+    pub fn to_pattern(&self) -> String {
+        self.result.iter()
+            .map(|res| match res {
+                MatchResult::Match => 'G',
+                MatchResult::Exists => 'Y',
+                MatchResult::None => 'X'
+            })
+            .collect()
+    }
+
+    /// Reads a result back from the form
+    /// [GuessResult::to_pattern] writes.
+    pub fn from_pattern(pattern: &str) -> Result<GuessResult, PatternError> {
+        let result = pattern.chars()
+            .map(|symbol| match symbol {
+                'G' => Ok(MatchResult::Match),
+                'Y' => Ok(MatchResult::Exists),
+                'X' => Ok(MatchResult::None),
+                _ => Err(PatternError { symbol })
+            })
+            .collect::<Result<Vec<MatchResult>, PatternError>>()?;
+        Ok(GuessResult { result })
+    }
+
+    /// Pretty-prints the result of a guess attempt,
+    /// with symbols marking the letters if `symbols`
+    /// is set (see [GameConfig::symbols]).
+    pub fn print_result_for(&self, word: &str, symbols: bool, output: &mut impl Write) {
+        say(output, self.render_for(word, symbols));
+    }
+
+    /// Renders the result of a guess attempt
+    /// as a row of colored letters.
+    fn render_for(&self, word: &str, symbols: bool) -> String {
+        let mut chars = word.graphemes(true).enumerate();
+
+        let letters: Vec<String> = self.result.iter()
+            .map(|_| GuessResult::get_and_colorize(&mut chars, self, symbols).to_string())
+            .collect();
+        letters.join(" ")
     }
 
-    /// Takes next letter from the character
+    /// Takes next letter from the grapheme
     /// iterator and colors it according to
-    /// the [GuessResult].
-    fn get_and_colorize(e: &mut Enumerate<Chars>, result: &GuessResult) -> ColoredString {
+    /// the [GuessResult], optionally marking
+    /// it with a symbol as well.
+    fn get_and_colorize(e: &mut Enumerate<Graphemes>, result: &GuessResult,
+                        symbols: bool) -> ColoredString {
         let tuple = e.next().unwrap();
         let ch = tuple.1;
         let res = *result.result
             .get(tuple.0).unwrap();
 
+        let letter = if symbols {
+            match res {
+                MatchResult::Match => format!("[{}]", ch),
+                MatchResult::Exists => format!("({})", ch),
+                MatchResult::None => format!(" {} ", ch)
+            }
+        } else {
+            String::from(ch)
+        };
+
         match res {
-            MatchResult::Match => String::from(ch).green().bold(),
-            MatchResult::Exists => String::from(ch).yellow().bold(),
-            MatchResult::None => String::from(ch).normal()
+            MatchResult::Match => letter.green().bold(),
+            MatchResult::Exists => letter.yellow().bold(),
+            MatchResult::None => letter.normal()
         }
     }
 
     /// Creates a [GuessResult] that starts
-    /// with all buckets filled with [MatchResult::Match]
-    fn new_all_green() -> GuessResult {
+    /// with all `len` buckets filled with [MatchResult::Match]
+    fn new_all_green(len: usize) -> GuessResult {
         let result = Vec::from_iter(
-            repeat(MatchResult::Match)
-                .take(5)
+            repeat_n(MatchResult::Match, len)
         );
         GuessResult {
             result
         }
     }
 
-    /// Creates an empty [GuessResult].
-    fn new_empty() -> GuessResult {
-        GuessResult { result: Vec::with_capacity(5) }
+    /// Creates an empty [GuessResult] with
+    /// room for `len` letters.
+    fn new_empty(len: usize) -> GuessResult {
+        GuessResult { result: Vec::with_capacity(len) }
     }
 
     /// Tracks a new letter [MatchResult].
@@ -252,20 +877,115 @@ impl GuessResult {
 
 }
 
+impl<'a> IntoIterator for &'a GuessResult {
+    type Item = &'a MatchResult;
+    type IntoIter = std::slice::Iter<'a, MatchResult>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.result.iter()
+    }
+}
+
+/// A custom [Error] type that reports a symbol
+/// [GuessResult::from_pattern] doesn't know.
+#[derive(Debug, PartialEq)]
+pub struct PatternError {
+    symbol: char
+}
+
+impl Display for PatternError {
+
+    /// Names the unknown symbol.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown pattern symbol '{}', expected G, Y or X", self.symbol)
+    }
+}
+
+impl Error for PatternError {}
+
+/// Tracks the best known [MatchResult] of
+/// every letter guessed so far in a game.
+#[derive(Debug, Default)]
+pub struct Keyboard {
+    letters: HashMap<char, MatchResult>
+}
+
+impl Keyboard {
+
+    /// Takes in the result of a guess. A letter's
+    /// status only ever goes up, so a letter seen
+    /// [MatchResult::Exists] turns into
+    /// [MatchResult::Match] once placed, but a
+    /// surplus grey copy never downgrades it.
+    ///
+    /// Letters made of several chars, such as one
+    /// with a combining accent, aren't tracked.
+    pub fn record(&mut self, word: &str, result: &GuessResult) {
+        for (letter, res) in word.graphemes(true).zip(result) {
+            let mut chars = letter.chars();
+            if let (Some(ch), None) = (chars.next(), chars.next()) {
+                let best = self.letters
+                    .entry(ch)
+                    .or_insert(*res);
+                *best = (*best).max(*res);
+            }
+        }
+    }
+
+    /// Shows the best known status of a letter,
+    /// or `None` if it hasn't been guessed yet.
+    pub fn status(&self, ch: char) -> Option<MatchResult> {
+        self.letters.get(&ch).copied()
+    }
+
+    /// Renders the a-z alphabet colored by the best
+    /// known letter statuses. If `symbols` is set (see
+    /// [GameConfig::symbols]), letters are also marked
+    /// `[x]` and `(x)`, and absent ones are blanked out.
+    pub fn render(&self, symbols: bool) -> String {
+        let letters: Vec<String> = ('a'..='z')
+            .map(|ch| {
+                let letter = if symbols {
+                    match self.status(ch) {
+                        Some(MatchResult::Match) => format!("[{}]", ch),
+                        Some(MatchResult::Exists) => format!("({})", ch),
+                        Some(MatchResult::None) => String::from(" · "),
+                        None => format!(" {} ", ch)
+                    }
+                } else {
+                    String::from(ch)
+                };
+                match self.status(ch) {
+                    Some(MatchResult::Match) => letter.green().bold(),
+                    Some(MatchResult::Exists) => letter.yellow().bold(),
+                    Some(MatchResult::None) => letter.bright_black(),
+                    None => letter.normal()
+                }.to_string()
+            })
+            .collect();
+        letters.join(" ")
+    }
+
+}
+
 /// Represents letter match result.
-#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+///
+/// Results are ordered by how much they tell
+/// about a letter: `None < Exists < Match`, so
+/// `max` picks the strongest one.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub enum MatchResult {
 
-    /// The guessed letter is in the secret
-    /// word at the correctly guessed position.
-    Match,
+    /// There is no such letter in the secret word.
+    None,
 
     /// Letter has been guessed correctly,
     /// but its position hasn't.
     Exists,
 
-    /// There is no such letter in the secret word.
-    None
+    /// The guessed letter is in the secret
+    /// word at the correctly guessed position.
+    Match
 
 }
 
@@ -274,123 +994,980 @@ pub enum MatchResult {
 
 
 
+#[cfg(test)]
 mod test {
-    use crate::game::{GuessResult, MatchResult, Word};
+    use std::collections::HashSet;
+    use std::io::Write;
+    use std::time::{Duration, Instant};
+    use crate::bank::{Dictionary, StaticDict};
+    use crate::bank::fixture::ListDict;
+    use crate::game::{ATTEMPT_COUNT, Command, GameConfig, GameLost, GuessResult, Keyboard,
+                      MatchResult, PatternError, Word, contradicted_greens, grade,
+                      hard_mode_violation, is_repeat_guess, normalize_guess, ordinal,
+                      parse_command, remaining_attempts, run_game_loop, run_timed_game_loop,
+                      solved_in, solved_on, strip_trailing_punctuation, with_elapsed};
+
+    /// Plays `input` against `secret` and returns
+    /// whether the game was won plus everything
+    /// the loop printed.
+    fn play(secret: &str, input: &[u8]) -> (bool, String) {
+        play_with(&GameConfig::default(), secret, input)
+    }
+
+    /// Same as [play], but with a custom [GameConfig].
+    fn play_with(config: &GameConfig, secret: &str, input: &[u8]) -> (bool, String) {
+        play_in(&StaticDict, config, secret, input)
+    }
+
+    /// Same as [play_with], but against a custom [Dictionary].
+    fn play_in(dict: &impl Dictionary, config: &GameConfig,
+               secret: &str, input: &[u8]) -> (bool, String) {
+        let word = Word::analyze_str(secret);
+        let mut output = Vec::new();
+
+        let result = run_game_loop(
+            &word,
+            dict,
+            config,
+            &mut &input[..],
+            &mut output
+        );
+
+        (result.won(), String::from_utf8(output).unwrap())
+    }
 
     #[test]
-    fn guess_result_full_match() {
-        let result = vec![
-            MatchResult::Match,
-            MatchResult::Match,
-            MatchResult::Match,
-            MatchResult::Match,
-            MatchResult::Match
-        ];
+    fn game_loop_invalid_utf8_reprompts() {
+        let (won, output) = play("bathe", b"\xff\xfe\xfd\xfc\xfb\nbathe\n");
 
-        let guess = GuessResult { result };
-        assert!(guess.full_match())
+        assert!(won);
+        assert!(output.contains("Couldn't read that - try again"));
+        assert!(output.contains("Solved in 1 guess"));
     }
 
     #[test]
-    fn guess_result_new_all_green() {
-        let guess = GuessResult::new_all_green();
-        assert!(guess.full_match())
+    fn game_loop_accepts_multibyte_guess() {
+        let dict = ListDict(vec!["crêpe"]);
+        let (won, output) = play_in(&dict, &GameConfig::default(), "crêpe", "crêpe\n".as_bytes());
+
+        assert!(won);
+        assert!(!output.contains("You'll need 5 characters"));
     }
 
     #[test]
-    fn guess_result_new_empty() {
-        let guess = GuessResult::new_empty();
-        assert!(guess.result.is_empty())
+    fn game_loop_accepts_decomposed_guess() {
+        let dict = ListDict(vec!["cre\u{302}pe"]);
+        let input = "cre\u{302}pe\n".as_bytes();
+        let (won, output) = play_in(&dict, &GameConfig::default(), "cre\u{302}pe", input);
+
+        assert!(won);
+        assert!(!output.contains("You'll need 5 characters"));
     }
 
     #[test]
-    #[should_panic]
-    fn word_analyze_not_5_characters() {
-        let word = String::from("bank");
-        Word::analyze(word);
+    fn game_loop_rejects_four_multibyte_chars() {
+        // 5 bytes, but only 4 characters
+        let (won, output) = play("bathe", "café\n".as_bytes());
+
+        assert!(!won);
+        assert!(output.contains("You'll need 5 characters"));
     }
 
     #[test]
-    #[should_panic]
-    fn word_analyze_str_not_5_characters() {
-        let word = "bank";
-        Word::analyze_str(word);
+    fn game_loop_rejects_unknown_word_without_attempt() {
+        let (won, output) = play("bathe", b"aaaaa\nbathe\n");
+
+        assert!(won);
+        assert!(output.contains("Not in word list"));
+        assert!(output.contains("Solved in 1 guess"));
     }
 
     #[test]
-    fn word_analyze_check_internal_word() {
-        let word = Word::analyze_str("bathe");
+    fn game_loop_rejects_very_long_guess() {
+        let mut input = "a".repeat(10_000).into_bytes();
+        input.extend_from_slice(b"\nbathe\n");
+        let (won, output) = play("bathe", &input);
+
+        assert!(won);
+        assert!(output.contains("You'll need 5 characters"));
+        assert!(output.contains("Solved in 1 guess"));
+    }
 
-        assert_eq!(
-            String::from("bathe"),
-            word.val
-        )
+    #[test]
+    fn game_loop_win_reports_guess_number() {
+        for guesses in 1..=ATTEMPT_COUNT {
+            let misses = ["crane", "braid", "lemon", "pious", "glyph"];
+            let mut input: String = misses[..guesses as usize - 1]
+                .iter()
+                .map(|miss| format!("{}\n", miss))
+                .collect();
+            input.push_str("bathe\n");
+            let (won, output) = play("bathe", input.as_bytes());
+
+            assert!(won);
+            assert!(output.contains(&format!("Solved in {} guess", guesses)));
+        }
     }
 
     #[test]
-    fn word_analyze_all_letters_different() {
+    fn game_loop_outcome_of_win() {
         let word = Word::analyze_str("bathe");
+        let outcome = run_game_loop(
+            &word,
+            &StaticDict,
+            &GameConfig::default(),
+            &mut &b"braid\ncrane\nbathe\n"[..],
+            &mut Vec::new()
+        );
 
-        assert_eq!(5, word.letters.len());
-
-        assert!(word.letters.contains_key(&'b'));
+        assert!(outcome.won());
+        assert_eq!(3, outcome.attempts);
         assert_eq!(
-            1,
-            word.letters
-                .get(&'b').unwrap()
-                .len()
+            vec![grades("bathe", "braid"), grades("bathe", "crane"), grades("bathe", "bathe")],
+            outcome.guesses
+                .into_iter()
+                .map(|guess| guess.result)
+                .collect::<Vec<_>>()
         );
-        assert!(word.letters
-            .get(&'b').unwrap()
-            .contains(&(0 as u8))
+    }
+
+    #[test]
+    fn game_loop_outcome_carries_elapsed_time() {
+        let word = Word::analyze_str("bathe");
+        let started = Instant::now();
+        let mut output = Vec::new();
+        let outcome = run_game_loop(
+            &word,
+            &StaticDict,
+            &GameConfig::default(),
+            &mut &b"crane\nbathe\n"[..],
+            &mut output
         );
 
-        assert!(word.letters.contains_key(&'a'));
-        assert_eq!(
+        assert!(outcome.elapsed <= started.elapsed());
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(&format!("in {:.1}s", outcome.elapsed.as_secs_f64())));
+    }
+
+    #[test]
+    fn game_loop_outcome_of_loss() {
+        let word = Word::analyze_str("bathe");
+        let config = GameConfig { attempts: 2, ..GameConfig::default() };
+        let outcome = run_game_loop(
+            &word,
+            &StaticDict,
+            &config,
+            &mut &b"braid\ncrane\n"[..],
+            &mut Vec::new()
+        );
+
+        assert!(!outcome.won());
+        assert_eq!(2, outcome.attempts);
+        assert_eq!(2, outcome.guesses.len());
+    }
+
+    #[test]
+    fn game_loop_custom_attempt_limit() {
+        let config = GameConfig { attempts: 2, ..GameConfig::default() };
+        let (won, output) = play_with(&config, "bathe", b"braid\ncrane\nbathe\n");
+
+        assert!(!won);
+        assert!(!output.contains("You won!"));
+    }
+
+    #[test]
+    fn game_loop_practice_never_runs_out() {
+        let config = GameConfig { practice: true, ..GameConfig::default() };
+        let misses = ["crane", "braid", "lemon", "pious", "glyph", "brick", "fjord", "waltz"];
+        let mut input: String = misses.iter()
+            .map(|miss| format!("{}\n", miss))
+            .collect();
+        input.push_str("bathe\n");
+        let (won, output) = play_with(&config, "bathe", input.as_bytes());
+
+        assert!(won);
+        assert!(!output.contains("You lost"));
+        assert!(output.contains("Solved in 9 guesses"));
+    }
+
+    #[test]
+    fn game_loop_practice_counts_past_255_guesses() {
+        let config = GameConfig { practice: true, ..GameConfig::default() };
+        let word = Word::analyze_str("bathe");
+        let mut input: String = StaticDict.iter_words()
+            .filter(|word| *word != "bathe")
+            .take(299)
+            .map(|word| format!("{}\n", word))
+            .collect();
+        input.push_str("bathe\n");
+        let mut output = Vec::new();
+        let outcome = run_game_loop(&word, &StaticDict, &config, &mut input.as_bytes(),
+                                    &mut output);
+
+        assert!(outcome.won());
+        assert_eq!(300, outcome.attempts);
+        assert!(String::from_utf8(output).unwrap().contains("Solved in 300 guesses"));
+    }
+
+    #[test]
+    fn parse_command_gives_up() {
+        assert_eq!(Some(Command::GiveUp), parse_command(":giveup"));
+        assert_eq!(Some(Command::GiveUp), parse_command(" :giveup\n"));
+        assert_eq!(None, parse_command("giveup"));
+        assert_eq!(None, parse_command("crane"));
+    }
+
+    /// Plays `input` against "bathe" with `config`
+    /// and returns how the game was lost.
+    fn give_up_with(config: &GameConfig, input: &[u8]) -> (u32, GameLost) {
+        let word = Word::analyze_str("bathe");
+        let outcome = run_game_loop(&word, &StaticDict, config, &mut &input[..],
+                                    &mut Vec::new());
+        (outcome.attempts, outcome.lost.unwrap())
+    }
+
+    #[test]
+    fn game_loop_give_up_reveals_word() {
+        let (attempts, lost) = give_up_with(&GameConfig::default(), b"crane\n:giveup\nbathe\n");
+
+        assert_eq!(1, attempts);
+        assert!(lost.gave_up());
+        assert!(lost.to_string().contains("You gave up"));
+        assert!(lost.to_string().contains("'bathe'"));
+    }
+
+    #[test]
+    fn game_loop_give_up_hides_secret() {
+        let config = GameConfig { hide_secret: true, ..GameConfig::default() };
+        let (_, lost) = give_up_with(&config, b"crane\n:giveup\n");
+
+        assert!(lost.gave_up());
+        assert!(lost.to_string().contains("You gave up"));
+        assert!(!lost.to_string().contains("bathe"));
+    }
+
+    #[test]
+    fn game_loop_out_of_attempts_is_not_giving_up() {
+        let config = GameConfig { attempts: 1, ..GameConfig::default() };
+        let word = Word::analyze_str("bathe");
+        let outcome = run_game_loop(&word, &StaticDict, &config, &mut &b"crane\n"[..],
+                                    &mut Vec::new());
+
+        assert!(!outcome.lost.unwrap().gave_up());
+    }
+
+    #[test]
+    fn game_loop_end_of_input_loses() {
+        let (won, _) = play("bathe", b"braid\n");
+        assert!(!won)
+    }
+
+    #[test]
+    fn game_loop_four_letter_word() {
+        let dict = ListDict(vec!["band", "bank", "bark"]);
+        let (won, output) = play_in(&dict, &GameConfig::default(), "bank", b"bathe\nband\nbank\n");
+
+        assert!(won);
+        assert!(output.contains("You'll need 4 characters"));
+        assert!(output.contains("Solved in 2 guesses"));
+    }
+
+    #[test]
+    fn game_loop_six_letter_word() {
+        let dict = ListDict(vec!["bathed", "bathes"]);
+        let input = b"bathe\nbathes\nbathed\n";
+        let (won, output) = play_in(&dict, &GameConfig::default(), "bathed", input);
+
+        assert!(won);
+        assert!(output.contains("You'll need 6 characters"));
+        assert!(output.contains("Solved in 2 guesses"));
+    }
+
+    #[test]
+    fn solved_in_first_guess() {
+        assert_eq!("Solved in 1 guess", solved_in(1))
+    }
+
+    #[test]
+    fn solved_in_fourth_guess() {
+        assert_eq!("Solved in 4 guesses", solved_in(4))
+    }
+
+    #[test]
+    fn solved_on_ordinal_guess() {
+        assert_eq!("Solved on the 1st guess", solved_on(1));
+        assert_eq!("Solved on the 3rd guess", solved_on(3));
+    }
+
+    #[test]
+    fn game_loop_ordinal_count_in_win_message() {
+        let config = GameConfig { ordinal_count: true, ..GameConfig::default() };
+        let (won, output) = play_with(&config, "bathe", b"crane\nbraid\nbathe\n");
+
+        assert!(won);
+        assert!(output.contains("Solved on the 3rd guess"));
+        assert!(!output.contains("Solved in"));
+    }
+
+    #[test]
+    fn remaining_attempts_two_used_of_six() {
+        let dots = remaining_attempts(2, 6);
+
+        assert_eq!(4, dots.matches('●').count());
+        assert_eq!(2, dots.matches('○').count());
+        assert_eq!("●●●●○○", dots)
+    }
+
+    /// Grades `guesses` against `secret` into a history.
+    fn history_of(secret: &str, guesses: &[&str]) -> Vec<(String, GuessResult)> {
+        let word = Word::analyze_str(secret);
+        guesses.iter()
+            .map(|guess| (String::from(*guess), word.try_match(&String::from(*guess))))
+            .collect()
+    }
+
+    #[test]
+    fn hard_mode_violation_dropped_green() {
+        let history = history_of("bathe", &["braid"]);
+        assert_eq!(
+            Some(String::from("Must use B in position 1")),
+            hard_mode_violation("crane", &history)
+        );
+    }
+
+    #[test]
+    fn hard_mode_violation_omitted_yellow() {
+        let history = history_of("bathe", &["braid"]);
+        assert_eq!(
+            Some(String::from("Guess must contain A")),
+            hard_mode_violation("bloke", &history)
+        );
+    }
+
+    #[test]
+    fn hard_mode_violation_repeated_letter() {
+        let history = history_of("sheep", &["eerie"]);
+        assert_eq!(
+            Some(String::from("Guess must contain E 2 times")),
+            hard_mode_violation("spend", &history)
+        );
+    }
+
+    #[test]
+    fn hard_mode_violation_consistent_guess() {
+        let history = history_of("bathe", &["braid", "balmy"]);
+        assert_eq!(None, hard_mode_violation("batch", &history));
+    }
+
+    #[test]
+    fn game_loop_hard_mode_rejects_without_attempt() {
+        let config = GameConfig { hard_mode: true, ..GameConfig::default() };
+        let (won, output) = play_with(&config, "bathe", b"braid\ncrane\nbathe\n");
+
+        assert!(won);
+        assert!(output.contains("Must use B in position 1"));
+        assert!(output.contains("Solved in 2 guesses"));
+    }
+
+    #[test]
+    fn contradicted_greens_for_moved_green() {
+        let word = Word::analyze_str("bathe");
+        let history = vec![(String::from("braid"), word.try_match(&String::from("braid")))];
+
+        assert_eq!(vec![(0, "b")], contradicted_greens("crane", &history));
+    }
+
+    #[test]
+    fn contradicted_greens_for_consistent_guess() {
+        let word = Word::analyze_str("bathe");
+        let history = vec![(String::from("braid"), word.try_match(&String::from("braid")))];
+
+        assert!(contradicted_greens("bloat", &history).is_empty());
+    }
+
+    #[test]
+    fn game_loop_warns_on_contradicted_green() {
+        let config = GameConfig { warn_known_greens: true, ..GameConfig::default() };
+        let (won, output) = play_with(&config, "bathe", b"braid\ncrane\nbathe\n");
+
+        assert!(won);
+        assert!(output.contains("Position 1 is known to be B"));
+    }
+
+    #[test]
+    fn game_loop_grants_grace_attempt_on_near_miss() {
+        let config = GameConfig { grace_attempt: true, ..GameConfig::default() };
+        let input = b"crane\nbraid\nlemon\npious\nglyph\nbaths\nbathe\n";
+        let (won, output) = play_with(&config, "bathe", input);
+
+        assert!(won);
+        assert!(output.contains("So close!"));
+        assert!(output.contains("Solved in 7 guesses"));
+    }
+
+    #[test]
+    fn game_loop_no_grace_attempt_past_largest_limit() {
+        let config = GameConfig {
+            attempts: u8::MAX,
+            grace_attempt: true,
+            ..GameConfig::default()
+        };
+        let mut input: String = StaticDict.iter_words()
+            .filter(|word| !word.starts_with("bath"))
+            .take(u8::MAX as usize - 1)
+            .map(|word| format!("{}\n", word))
+            .collect();
+        input.push_str("baths\nbathe\n");
+        let (won, output) = play_with(&config, "bathe", input.as_bytes());
+
+        assert!(!won);
+        assert!(!output.contains("So close!"));
+    }
+
+    #[test]
+    fn game_loop_no_grace_attempt_without_near_miss() {
+        let config = GameConfig { grace_attempt: true, ..GameConfig::default() };
+        let input = b"crane\nlemon\npious\nglyph\nbrick\nbraid\nbathe\n";
+        let (won, output) = play_with(&config, "bathe", input);
+
+        assert!(!won);
+        assert!(!output.contains("So close!"));
+    }
+
+    #[test]
+    fn game_loop_hide_secret_spoils_nothing() {
+        let word = Word::analyze_str("bathe");
+        let config = GameConfig { hide_secret: true, ..GameConfig::default() };
+        let mut output = Vec::new();
+
+        let result = run_game_loop(&word, &StaticDict, &config, &mut &b"crane\n"[..], &mut output);
+        let lost = result.lost.unwrap();
+        write!(output, "{}", lost).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("You lost :("));
+        assert!(!output.contains("bathe"));
+    }
+
+    #[test]
+    fn game_lost_reveals_secret_by_default() {
+        let word = Word::analyze_str("bathe");
+        let (_, output) = play("bathe", b"crane\n");
+
+        assert!(!output.contains("bathe"));
+        assert!(GameLost::with_word(&word, true).to_string().contains("bathe"));
+    }
+
+    #[test]
+    fn normalize_guess_trims_and_lowercases() {
+        assert_eq!("bathe", normalize_guess("BATHE"));
+        assert_eq!("bathe", normalize_guess("  BaThE\r\n"));
+        assert_eq!("crêpe", normalize_guess("CRÊPE"));
+    }
+
+    #[test]
+    fn game_loop_mixed_case_guess_matches() {
+        let (won, output) = play("bathe", b"BaThE\n");
+
+        assert!(won);
+        assert!(output.contains("Solved in 1 guess"));
+    }
+
+    #[test]
+    fn is_repeat_guess_detects_repeats() {
+        let mut guessed = HashSet::new();
+
+        assert!(!is_repeat_guess(&mut guessed, "crane"));
+        assert!(!is_repeat_guess(&mut guessed, "braid"));
+        assert!(is_repeat_guess(&mut guessed, "crane"));
+    }
+
+    #[test]
+    fn game_loop_repeat_guess_costs_no_attempt() {
+        let config = GameConfig { attempts: 2, ..GameConfig::default() };
+        let (won, output) = play_with(&config, "bathe", b"crane\nCRANE\ncrane\nbathe\n");
+
+        assert!(won);
+        assert_eq!(2, output.matches("You already guessed that").count());
+        assert!(output.contains("Solved in 2 guesses"));
+    }
+
+    #[test]
+    fn game_loop_prints_normalized_guess() {
+        let config = GameConfig { echo_guess: true, ..GameConfig::default() };
+        let (_, output) = play_with(&config, "bathe", b"BRAID\n");
+        assert_eq!(Some("braid"), output.lines().next())
+    }
+
+    #[test]
+    fn strip_trailing_punctuation_drops_pasted_suffix() {
+        assert_eq!("bathe", strip_trailing_punctuation("bathe."));
+        assert_eq!("bathe", strip_trailing_punctuation("bathe!\"'"));
+        assert_eq!("bathe", strip_trailing_punctuation("bathe"));
+    }
+
+    #[test]
+    fn game_loop_strip_punctuation_accepts_pasted_guess() {
+        let config = GameConfig { strip_punctuation: true, ..GameConfig::default() };
+        let (won, _) = play_with(&config, "bathe", b"bathe.\n");
+        assert!(won)
+    }
+
+    #[test]
+    fn game_loop_rejects_pasted_guess_by_default() {
+        let (won, output) = play("bathe", b"bathe.\n");
+
+        assert!(!won);
+        assert!(output.contains("You'll need 5 characters"));
+    }
+
+    #[test]
+    fn match_result_ordering() {
+        assert!(MatchResult::None < MatchResult::Exists);
+        assert!(MatchResult::Exists < MatchResult::Match);
+        assert_eq!(MatchResult::Match, MatchResult::Exists.max(MatchResult::Match));
+    }
+
+    #[test]
+    fn game_loop_plays_fixed_opener_first() {
+        let word = Word::analyze_str("bathe");
+        let config = GameConfig {
+            fixed_opener: Some(String::from("crane")),
+            ..GameConfig::default()
+        };
+        let (won, output) = play_with(&config, "bathe", b"bathe\n");
+
+        let mut opener_row = Vec::new();
+        word.try_match(&String::from("crane"))
+            .print_result_for("crane", false, &mut opener_row);
+        let opener_row = String::from_utf8(opener_row).unwrap();
+
+        assert!(won);
+        let mut lines = output.lines().skip(1);
+        assert_eq!(opener_row.trim_end(), lines.next().unwrap());
+        assert!(output.contains("Solved in 2 guesses"));
+    }
+
+    #[test]
+    fn ordinal_suffixes() {
+        assert_eq!("1st", ordinal(1));
+        assert_eq!("2nd", ordinal(2));
+        assert_eq!("3rd", ordinal(3));
+        assert_eq!("4th", ordinal(4));
+        assert_eq!("11th", ordinal(11));
+        assert_eq!("22nd", ordinal(22));
+    }
+
+    #[test]
+    fn guess_result_render_for_symbols() {
+        let word = Word::analyze_str("bathe");
+        let row = word.try_match(&String::from("braid"))
+            .render_for("braid", true);
+
+        assert!(row.contains("[b]"));
+        assert!(row.contains(" r "));
+        assert!(row.contains("(a)"));
+        assert!(row.contains(" i "));
+        assert!(row.contains(" d "));
+    }
+
+    #[test]
+    fn guess_result_render_for_plain_has_no_symbols() {
+        let word = Word::analyze_str("bathe");
+        let row = word.try_match(&String::from("braid"))
+            .render_for("braid", false);
+
+        assert!(!row.contains('['));
+        assert!(!row.contains('('));
+    }
+
+    #[test]
+    fn guess_result_iter_in_order() {
+        use MatchResult::{Exists, Match, None};
+
+        let result = grade("bathe", "braid");
+        let expected = vec![Match, None, Exists, None, None];
+
+        assert_eq!(expected, result.iter().copied().collect::<Vec<_>>());
+
+        let mut looped = Vec::new();
+        for res in &result {
+            looped.push(*res);
+        }
+        assert_eq!(expected, looped);
+    }
+
+    #[test]
+    fn guess_result_to_pattern() {
+        assert_eq!("GXYXX", grade("bathe", "braid").to_pattern());
+        assert_eq!("GGGGG", grade("bathe", "bathe").to_pattern());
+    }
+
+    #[test]
+    fn guess_result_pattern_round_trip() {
+        for pattern in ["GXYXX", "GGGGG", "XXXXX", "YYXGY", "GXYG", "XYGXYG"] {
+            assert_eq!(pattern, GuessResult::from_pattern(pattern).unwrap().to_pattern());
+        }
+    }
+
+    #[test]
+    fn guess_result_from_pattern_rejects_unknown_symbol() {
+        assert_eq!(
+            PatternError { symbol: 'g' },
+            GuessResult::from_pattern("GXgXX").unwrap_err()
+        );
+        assert!(GuessResult::from_pattern("GX-XX").is_err());
+    }
+
+    #[test]
+    fn guess_result_describe_mixed() {
+        let word = Word::analyze_str("bathe");
+        let result = word.try_match(&String::from("braid"));
+
+        assert_eq!(
+            "1st letter B correct, \
+             2nd letter R not in word, \
+             3rd letter A in word but elsewhere, \
+             4th letter I not in word, \
+             5th letter D not in word",
+            result.describe("braid")
+        );
+    }
+
+    #[test]
+    fn game_loop_describe_results_prints_description() {
+        let config = GameConfig { describe_results: true, ..GameConfig::default() };
+        let (_, output) = play_with(&config, "bathe", b"braid\n");
+        assert!(output.contains("1st letter B correct"))
+    }
+
+    #[test]
+    fn game_loop_echoes_guess_above_result() {
+        let config = GameConfig { echo_guess: true, ..GameConfig::default() };
+        let (_, output) = play_with(&config, "bathe", b"braid\n");
+
+        let mut lines = output.lines();
+        assert_eq!(Some("braid"), lines.next());
+        assert!(lines.next().unwrap().contains('b'));
+    }
+
+    #[test]
+    fn game_loop_does_not_echo_by_default() {
+        let (_, output) = play("bathe", b"braid\n");
+        assert!(!output.lines().any(|line| line == "braid"))
+    }
+
+    #[test]
+    fn game_loop_blind_holds_back_results() {
+        let config = GameConfig {
+            blind: true,
+            describe_results: true,
+            ..GameConfig::default()
+        };
+        let (won, output) = play_with(&config, "bathe", b"braid\nbaths\nbathe\n");
+
+        assert!(won);
+        let (before, board) = output.split_once("Your board:").unwrap();
+        assert!(!before.contains("letter"));
+        assert_eq!(3, board.lines().filter(|line| line.contains("letter")).count());
+        assert!(board.contains("1st letter B correct, 2nd letter R not in word"));
+    }
+
+    #[test]
+    fn game_loop_blind_ignores_grace_attempt() {
+        let config = GameConfig {
+            attempts: 2,
+            grace_attempt: true,
+            blind: true,
+            ..GameConfig::default()
+        };
+        // "baths" is a near miss on the last attempt, but a
+        // blind game ends there just like on a plain miss
+        let (won, output) = play_with(&config, "bathe", b"braid\nbaths\nbathe\n");
+
+        assert!(!won);
+        assert!(!output.contains("So close!"));
+        assert!(output.contains("Your board:"));
+    }
+
+    #[test]
+    fn game_loop_blind_ignores_hard_mode() {
+        let config = GameConfig { blind: true, hard_mode: true, ..GameConfig::default() };
+        // "crane" drops the green B found by "braid"
+        let (won, output) = play_with(&config, "bathe", b"braid\ncrane\nbathe\n");
+
+        assert!(won);
+        let (before, _) = output.split_once("Your board:").unwrap();
+        assert!(!before.contains("Must use"));
+        assert!(!before.contains("must contain"));
+        assert!(output.contains("Solved in 3 guesses"));
+    }
+
+    #[test]
+    fn game_loop_blind_reveals_board_on_loss() {
+        let config = GameConfig { blind: true, ..GameConfig::default() };
+        let (won, output) = play_with(&config, "bathe", b"braid\ncrane\n");
+
+        assert!(!won);
+        let (before, board) = output.split_once("Your board:").unwrap();
+        assert!(before.trim().is_empty());
+        assert_eq!(2, board.lines().filter(|line| !line.is_empty()).count());
+    }
+
+    #[test]
+    fn with_elapsed_annotates_line() {
+        let line = with_elapsed(String::from("b r a i d"), Duration::from_millis(3200));
+        assert_eq!("b r a i d  3.2s", line)
+    }
+
+    #[test]
+    fn game_loop_show_timing_annotates_rows() {
+        let config = GameConfig {
+            show_timing: true,
+            describe_results: true,
+            ..GameConfig::default()
+        };
+        // every reading of the clock is 3.2s after the last
+        let start = Instant::now();
+        let mut ticks = 0;
+        let mut clock = || {
+            ticks += 1;
+            start + Duration::from_millis(3200) * ticks
+        };
+        let mut output = Vec::new();
+        run_timed_game_loop(
+            &Word::analyze_str("bathe"),
+            &StaticDict,
+            &config,
+            &mut &b"braid\n"[..],
+            &mut output,
+            &mut clock
+        );
+
+        let output = String::from_utf8(output).unwrap();
+        let row = output.lines().next().unwrap();
+        assert!(row.starts_with("1st letter B correct"));
+        assert!(row.ends_with("not in word  3.2s"));
+    }
+
+    #[test]
+    fn keyboard_records_best_status() {
+        let word = Word::analyze_str("bathe");
+        let mut keyboard = Keyboard::default();
+
+        keyboard.record("tribe", &word.try_match(&String::from("tribe")));
+        assert_eq!(Some(MatchResult::Exists), keyboard.status('t'));
+        assert_eq!(Some(MatchResult::Exists), keyboard.status('b'));
+        assert_eq!(Some(MatchResult::None), keyboard.status('r'));
+        assert_eq!(Some(MatchResult::Match), keyboard.status('e'));
+
+        // "latte" places the first t and greys the second
+        keyboard.record("latte", &word.try_match(&String::from("latte")));
+        assert_eq!(Some(MatchResult::Match), keyboard.status('t'));
+        assert_eq!(Some(MatchResult::Match), keyboard.status('a'));
+        assert_eq!(Some(MatchResult::None), keyboard.status('l'));
+        assert_eq!(Some(MatchResult::Exists), keyboard.status('b'));
+        assert_eq!(None, keyboard.status('z'));
+    }
+
+    #[test]
+    fn keyboard_never_downgrades() {
+        let word = Word::analyze_str("bathe");
+        let mut keyboard = Keyboard::default();
+
+        keyboard.record("baths", &word.try_match(&String::from("baths")));
+        keyboard.record("abbey", &word.try_match(&String::from("abbey")));
+
+        assert_eq!(Some(MatchResult::Match), keyboard.status('b'));
+        assert_eq!(Some(MatchResult::Match), keyboard.status('a'));
+    }
+
+    #[test]
+    fn game_loop_prints_keyboard_each_turn() {
+        let (_, output) = play("bathe", b"braid\ncrane\n");
+        let keyboard_rows = output.lines()
+            .filter(|line| line.contains('z') && line.contains('q'))
+            .count();
+        assert_eq!(2, keyboard_rows);
+    }
+
+    #[test]
+    fn keyboard_render_for_symbols() {
+        let mut keyboard = Keyboard::default();
+        keyboard.record("tribe", &grade("bathe", "tribe"));
+
+        let row = keyboard.render(true);
+        assert!(row.contains("(b)"));
+        assert!(row.contains("[e]"));
+        assert!(row.contains(" a "));
+        assert!(!row.contains('r'));
+    }
+
+    #[test]
+    fn game_loop_describe_results_skips_keyboard() {
+        let config = GameConfig { describe_results: true, ..GameConfig::default() };
+        let (_, output) = play_with(&config, "bathe", b"braid\ncrane\n");
+
+        assert_eq!(2, output.lines().count());
+        assert!(output.lines().all(|line| line.contains("letter")));
+    }
+
+    #[test]
+    fn guess_result_full_match() {
+        let result = vec![
+            MatchResult::Match,
+            MatchResult::Match,
+            MatchResult::Match,
+            MatchResult::Match,
+            MatchResult::Match
+        ];
+
+        let guess = GuessResult { result };
+        assert!(guess.full_match())
+    }
+
+    #[test]
+    fn guess_result_new_all_green() {
+        let guess = GuessResult::new_all_green(5);
+        assert!(guess.full_match());
+        assert_eq!(5, guess.result.len())
+    }
+
+    #[test]
+    fn guess_result_new_empty() {
+        let guess = GuessResult::new_empty(5);
+        assert!(guess.result.is_empty())
+    }
+
+    #[test]
+    #[should_panic]
+    fn word_analyze_empty() {
+        let word = String::new();
+        Word::analyze(word);
+    }
+
+    #[test]
+    #[should_panic]
+    fn word_analyze_str_empty() {
+        Word::analyze_str("");
+    }
+
+    #[test]
+    fn word_len_counts_characters() {
+        assert_eq!(4, Word::analyze_str("bank").len());
+        assert_eq!(5, Word::analyze_str("crêpe").len());
+        assert_eq!(6, Word::analyze_str("bathed").len());
+    }
+
+    #[test]
+    fn word_analyze_multibyte_five_characters() {
+        let word = Word::analyze_str("crêpe");
+
+        assert_eq!(5, word.letters.len());
+        assert!(word.letters.get("ê").unwrap().contains(&2));
+    }
+
+    #[test]
+    fn word_analyze_decomposed_five_letters() {
+        // 'e' followed by a combining circumflex
+        let word = Word::analyze_str("cre\u{302}pe");
+
+        assert_eq!(5, word.len());
+        assert!(word.letters.get("e\u{302}").unwrap().contains(&2));
+        assert!(word.letters.get("e").unwrap().contains(&4));
+    }
+
+    #[test]
+    fn word_try_match_decomposed_letter() {
+        use MatchResult::{Match, None};
+
+        assert_eq!(vec![Match; 5], grades("cre\u{302}pe", "cre\u{302}pe"));
+        // precomposed and decomposed spellings are different letters,
+        // but both still count as one position each
+        assert_eq!(vec![Match, Match, None, Match, Match], grades("crêpe", "cre\u{302}pe"));
+    }
+
+    #[test]
+    fn guess_result_render_for_decomposed_letter() {
+        let row = grade("cre\u{302}pe", "cre\u{302}pe")
+            .render_for("cre\u{302}pe", true);
+        assert!(row.contains("[e\u{302}]"));
+    }
+
+    #[test]
+    fn word_analyze_check_internal_word() {
+        let word = Word::analyze_str("bathe");
+
+        assert_eq!(
+            String::from("bathe"),
+            word.val
+        )
+    }
+
+    #[test]
+    fn word_analyze_all_letters_different() {
+        let word = Word::analyze_str("bathe");
+
+        assert_eq!(5, word.letters.len());
+
+        assert!(word.letters.contains_key("b"));
+        assert_eq!(
+            1,
+            word.letters
+                .get("b").unwrap()
+                .len()
+        );
+        assert!(word.letters
+            .get("b").unwrap()
+            .contains(&0)
+        );
+
+        assert!(word.letters.contains_key("a"));
+        assert_eq!(
             1,
             word.letters
-                .get(&'a').unwrap()
+                .get("a").unwrap()
                 .len()
         );
         assert!(word.letters
-            .get(&'a').unwrap()
-            .contains(&(1 as u8))
+            .get("a").unwrap()
+            .contains(&1)
         );
 
-        assert!(word.letters.contains_key(&'t'));
+        assert!(word.letters.contains_key("t"));
         assert_eq!(
             1,
             word.letters
-                .get(&'t').unwrap()
+                .get("t").unwrap()
                 .len()
         );
         assert!(word.letters
-            .get(&'t').unwrap()
-            .contains(&(2 as u8))
+            .get("t").unwrap()
+            .contains(&2)
         );
 
-        assert!(word.letters.contains_key(&'h'));
+        assert!(word.letters.contains_key("h"));
         assert_eq!(
             1,
             word.letters
-                .get(&'h').unwrap()
+                .get("h").unwrap()
                 .len()
         );
         assert!(word.letters
-            .get(&'h').unwrap()
-            .contains(&(3 as u8))
+            .get("h").unwrap()
+            .contains(&3)
         );
 
-        assert!(word.letters.contains_key(&'e'));
+        assert!(word.letters.contains_key("e"));
         assert_eq!(
             1,
             word.letters
-                .get(&'e').unwrap()
+                .get("e").unwrap()
                 .len()
         );
         assert!(word.letters
-            .get(&'e').unwrap()
-            .contains(&(4 as u8))
+            .get("e").unwrap()
+            .contains(&4)
         );
 
     }
@@ -400,12 +1977,12 @@ mod test {
         let word = Word::analyze_str("aaaaa");
 
         assert_eq!(1, word.letters.len());
-        assert!(word.letters.contains_key(&'a'));
+        assert!(word.letters.contains_key("a"));
 
-        let mut as_sorted_vec: Vec<u8> = word.letters
-            .get(&'a').unwrap()
-            .into_iter()
-            .map(|ptr|*ptr)
+        let mut as_sorted_vec: Vec<usize> = word.letters
+            .get("a").unwrap()
+            .iter()
+            .copied()
             .collect();
         as_sorted_vec.sort_unstable();
 
@@ -413,6 +1990,16 @@ mod test {
         assert_eq!(as_sorted_vec, vec![0, 1, 2, 3, 4]);
     }
 
+    #[test]
+    fn word_letter_counts() {
+        let counts = Word::analyze_str("llama").letter_counts();
+
+        assert_eq!(3, counts.len());
+        assert_eq!(Some(&2), counts.get("l"));
+        assert_eq!(Some(&2), counts.get("a"));
+        assert_eq!(Some(&1), counts.get("m"));
+    }
+
     #[test]
     fn word_try_match_full_match() {
         let word = Word::analyze_str("bathe");
@@ -422,6 +2009,128 @@ mod test {
         assert!(result.full_match())
     }
 
+    /// Grades `guess` against `secret` and lists the results.
+    fn grades(secret: &str, guess: &str) -> Vec<MatchResult> {
+        grade(secret, guess).result
+    }
+
+    #[test]
+    fn grade_matches_try_match() {
+        for (secret, guess) in [("bathe", "braid"), ("abide", "geese"), ("sheep", "eerie"),
+                                ("balls", "llama"), ("bathe", "bathe")] {
+            let word = Word::analyze_str(secret);
+            assert_eq!(
+                word.try_match(&String::from(guess)).result,
+                grade(secret, guess).result
+            );
+        }
+    }
+
+    #[test]
+    fn grade_duplicate_letters() {
+        use MatchResult::{Exists, Match, None};
+
+        assert_eq!(vec![Exists, Match, None, None, Match], grades("geese", "eerie"));
+        assert_eq!(vec![Exists, Match, Exists, None, Match], grades("robot", "boost"));
+        assert_eq!(vec![None, Exists, None, None, None], grades("audio", "mamma"));
+    }
+
+    #[test]
+    fn word_try_match_surplus_letter_is_none() {
+        use MatchResult::{Match, None};
+
+        assert_eq!(
+            vec![None, None, None, None, Match],
+            grades("abide", "geese")
+        );
+    }
+
+    #[test]
+    fn word_try_match_duplicates_within_secret_count() {
+        use MatchResult::{Exists, None};
+
+        // "ball" from the report is four letters, so
+        // its five-letter plural stands in for it
+        assert_eq!(
+            vec![Exists, Exists, Exists, None, None],
+            grades("balls", "llama")
+        );
+    }
+
+    #[test]
+    fn word_try_match_three_guessed_two_in_secret() {
+        use MatchResult::{Exists, Match, None};
+
+        assert_eq!(
+            vec![Exists, Exists, None, None, None],
+            grades("sheep", "eerie")
+        );
+        assert_eq!(
+            vec![None, Exists, Match, Exists, None],
+            grades("sheep", "geese")
+        );
+    }
+
+    #[test]
+    fn word_try_match_four_letters() {
+        use MatchResult::{Exists, Match, None};
+
+        assert_eq!(vec![Match, Match, Match, Match], grades("bank", "bank"));
+        assert_eq!(vec![Exists, Match, Match, None], grades("bank", "kana"));
+    }
+
+    #[test]
+    fn word_try_match_six_letters() {
+        use MatchResult::{Exists, Match, None};
+
+        assert_eq!(
+            vec![Match, Match, Match, Match, Match, Match],
+            grades("bathed", "bathed")
+        );
+        assert_eq!(
+            vec![Exists, Exists, None, Exists, None, None],
+            grades("bathed", "abbeys")
+        );
+    }
+
+    #[test]
+    fn word_try_match_past_256_letters() {
+        let filler = "a".repeat(255);
+        let secret = format!("b{}c", filler);
+        let guess = format!("c{}b", filler);
+
+        let result = grades(&secret, &guess);
+        assert_eq!(MatchResult::Exists, result[0]);
+        assert_eq!(MatchResult::Exists, result[256]);
+        assert!(result[1..256].iter().all(|res| *res == MatchResult::Match));
+    }
+
+    #[test]
+    fn word_is_consistent_with_surviving_candidate() {
+        let result = grade("bathe", "crane");
+
+        assert!(Word::analyze_str("bathe").is_consistent_with("crane", &result));
+        assert!(Word::analyze_str("lathe").is_consistent_with("crane", &result));
+    }
+
+    #[test]
+    fn word_is_consistent_with_gray_letter_rules_out() {
+        let result = grade("bathe", "crane");
+
+        // 'c' came back gray, so no secret can have it
+        assert!(!Word::analyze_str("caste").is_consistent_with("crane", &result));
+    }
+
+    #[test]
+    fn word_is_consistent_with_duplicate_letters() {
+        // only the last 'e' of "geese" lit up, so the
+        // secret has exactly one 'e', at the end
+        let result = grade("abide", "geese");
+
+        assert!(Word::analyze_str("abide").is_consistent_with("geese", &result));
+        assert!(!Word::analyze_str("elite").is_consistent_with("geese", &result));
+    }
+
     #[test]
     fn word_try_match_partial_match() {
         let word = Word::analyze_str("bathe");
@@ -432,7 +2141,7 @@ mod test {
 
         assert_eq!(
             MatchResult::Match,
-            *result.result.get(0).unwrap()
+            *result.result.first().unwrap()
         );
         assert_eq!(
             MatchResult::None,
@@ -0,0 +1,3 @@
+pub mod bank;
+pub mod game;
+pub mod stats;
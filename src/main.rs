@@ -1,25 +1,195 @@
-
-mod bank;
-mod game;
-
+use std::env;
+use std::path::PathBuf;
 use std::process::exit;
-use crate::bank::{Dictionary, StaticDict};
-use crate::game::start_game_loop;
+use std::time::SystemTime;
+use wordle::bank::{Dictionary, FileDict, StaticDict, daily_seed, has_min_vowels,
+                   is_pronounceable};
+use wordle::game::{GameConfig, Outcome, start_game_loop};
+use wordle::stats::{Stats, stats_path};
 
 fn main() {
 
+    let config = match parse_args(env::args().skip(1)) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(2);
+        }
+    };
+
     println!("Welcome to Wordle!");
 
-    let dict = StaticDict;
-    let secret = dict.generate();
+    match &config.word_list {
+        Some(path) => match FileDict::open(path) {
+            Ok(dict) => play(&dict, &config),
+            Err(e) => {
+                eprintln!("{}", e);
+                exit(2);
+            }
+        },
+        None => play(&StaticDict, &config)
+    }
+
+}
+
+/// Picks a secret out of `dict` and plays
+/// a game of it, keeping the stats.
+fn play(dict: &impl Dictionary, config: &GameConfig) {
+    if let Some(opener) = &config.fixed_opener {
+        if !dict.contains(opener) {
+            eprintln!("Opener '{}' is not in the word list", opener);
+            exit(2);
+        }
+    }
+
+    let accept = |word: &_| {
+        has_min_vowels(word, config.min_vowels)
+            && (!config.pronounceable || is_pronounceable(word))
+    };
+    let picked = if config.daily {
+        dict.generate_seeded(daily_seed(SystemTime::now()))
+    } else if config.min_vowels == 0 && !config.pronounceable {
+        // nothing to filter, so don't go over every word
+        dict.generate()
+    } else {
+        dict.generate_where(accept)
+    };
+    let secret = match picked {
+        Ok(secret) => secret,
+        Err(e) => {
+            eprintln!("Couldn't pick a word: {}", e);
+            exit(1);
+        }
+    };
+
+    println!("{}", vec!["_"; secret.len()].join(" "));
+
+    let outcome = start_game_loop(&secret, dict, config);
+    if let Some(lost) = &outcome.lost {
+        println!("{} after {:.1}s", lost, outcome.elapsed.as_secs_f64());
+    }
+
+    if !config.practice {
+        record_stats(&outcome);
+    }
+}
 
-    println!("_ _ _ _ _");
+/// Counts the game into the stats kept
+/// in the config dir and prints them.
+fn record_stats(outcome: &Outcome) {
+    let Some(path) = stats_path() else {
+        return;
+    };
 
-    match start_game_loop(&secret) {
-        Ok(_) => exit(0),
+    let mut stats = match Stats::load_from(&path) {
+        Ok(stats) => stats,
         Err(e) => {
-            println!("{}", e);
+            eprintln!("Couldn't load stats: {}", e);
+            return;
+        }
+    };
+
+    stats.record(outcome);
+    if let Err(e) = stats.save_to(&path) {
+        eprintln!("Couldn't save stats: {}", e);
+    }
+
+    print!("{}", stats);
+}
+
+/// Builds a [GameConfig] out of
+/// the command line arguments.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<GameConfig, String> {
+    let mut config = GameConfig::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dots" => config.show_remaining = true,
+            "--warn-greens" => config.warn_known_greens = true,
+            "--grace" => config.grace_attempt = true,
+            "--hard" => config.hard_mode = true,
+            "--spoiler-free" => config.hide_secret = true,
+            "--strip-punctuation" => config.strip_punctuation = true,
+            "--timer" => config.show_timing = true,
+            "--symbols" => config.symbols = true,
+            "--practice" => config.practice = true,
+            "--ordinal" => config.ordinal_count = true,
+            "--attempts" => {
+                config.attempts = args.next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|n| *n >= 1)
+                    .ok_or("--attempts needs a number of at least 1")?;
+            },
+            "--blind" => config.blind = true,
+            "--daily" => config.daily = true,
+            "--describe" => config.describe_results = true,
+            "--echo" => config.echo_guess = true,
+            "--min-vowels" => {
+                config.min_vowels = args.next()
+                    .and_then(|n| n.parse().ok())
+                    .ok_or("--min-vowels needs a number")?;
+            },
+            "--pronounceable" => config.pronounceable = true,
+            "--words" => {
+                let path = args.next()
+                    .ok_or("--words needs a path to a word list")?;
+                config.word_list = Some(PathBuf::from(path));
+            },
+            "--opener" => {
+                // whether it is a known word of the right
+                // length is up to the dictionary and the game
+                let opener = args.next()
+                    .ok_or("--opener needs a word")?
+                    .trim()
+                    .to_lowercase();
+                config.fixed_opener = Some(opener);
+            },
+            _ => return Err(format!("Unknown argument '{}'", arg))
         }
     }
 
-}
\ No newline at end of file
+    if config.daily && (config.min_vowels > 0 || config.pronounceable) {
+        return Err(String::from("The daily word can't be filtered"));
+    }
+
+    if config.hard_mode && config.blind {
+        return Err(String::from("Hard mode can't be played blind"));
+    }
+
+    if config.grace_attempt && config.blind {
+        return Err(String::from("A grace attempt can't be granted blind"));
+    }
+
+    Ok(config)
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::{GameConfig, parse_args};
+
+    /// Runs [parse_args] over `args`.
+    fn parse(args: &[&str]) -> Result<GameConfig, String> {
+        parse_args(args.iter().map(|arg| String::from(*arg)))
+    }
+
+    #[test]
+    fn parse_args_rejects_hard_blind() {
+        assert!(parse(&["--hard", "--blind"]).is_err());
+        assert!(parse(&["--hard"]).unwrap().hard_mode);
+        assert!(parse(&["--blind"]).unwrap().blind);
+    }
+
+    #[test]
+    fn parse_args_accepts_any_opener() {
+        assert_eq!(Some(String::from("crêpe")), parse(&["--opener", "Crêpe"]).unwrap().fixed_opener);
+        assert_eq!(Some(String::from("bath")), parse(&["--opener", "bath"]).unwrap().fixed_opener);
+        assert!(parse(&["--opener"]).is_err());
+    }
+
+    #[test]
+    fn parse_args_rejects_grace_blind() {
+        assert!(parse(&["--grace", "--blind"]).is_err());
+        assert!(parse(&["--grace"]).unwrap().grace_attempt);
+    }
+}
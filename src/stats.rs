@@ -0,0 +1,260 @@
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use crate::game::Outcome;
+
+/// Name of the stats file inside
+/// the config directory.
+const STATS_FILE: &str = "wordle/stats";
+
+/// Widest bar of the guess distribution
+/// printed by [Stats], in characters.
+const BAR_WIDTH: u32 = 20;
+
+/// Win/loss statistics kept across games.
+#[derive(Debug, Default, PartialEq)]
+pub struct Stats {
+
+    /// Amount of games finished.
+    pub played: u32,
+
+    /// Amount of games won.
+    pub won: u32,
+
+    /// Games won in a row up to now.
+    pub current_streak: u32,
+
+    /// Longest run of games won in a row.
+    pub max_streak: u32,
+
+    /// How many games were won on each guess:
+    /// `distribution[0]` counts the games won
+    /// on the 1st guess, and so on.
+    pub distribution: Vec<u32>
+
+}
+
+impl Stats {
+
+    /// Loads the stats from `path`, starting
+    /// afresh if there is no such file yet.
+    pub fn load_from(path: &Path) -> io::Result<Stats> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Stats::parse(&contents)
+                .ok_or_else(|| io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Malformed stats file {}", path.display())
+                )),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Stats::default()),
+            Err(e) => Err(e)
+        }
+    }
+
+    /// Saves the stats to `path`, creating
+    /// its directory if needed.
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.serialize())
+    }
+
+    /// Counts in the outcome of a game: a win extends
+    /// the streak and lands in the bucket of the guess
+    /// it took, a loss breaks the streak.
+    pub fn record(&mut self, outcome: &Outcome) {
+        self.played += 1;
+
+        if outcome.won() {
+            self.won += 1;
+            self.current_streak += 1;
+            self.max_streak = self.max_streak.max(self.current_streak);
+
+            let bucket = outcome.attempts.max(1) as usize - 1;
+            if self.distribution.len() <= bucket {
+                self.distribution.resize(bucket + 1, 0);
+            }
+            self.distribution[bucket] += 1;
+        } else {
+            self.current_streak = 0;
+        }
+    }
+
+    /// Share of the games played that were
+    /// won, in percent, rounded down.
+    pub fn win_rate(&self) -> u32 {
+        self.won * 100 / self.played.max(1)
+    }
+
+    /// Writes the stats out as `key value` lines,
+    /// the distribution as space-separated counts.
+    fn serialize(&self) -> String {
+        let distribution: Vec<String> = self.distribution.iter()
+            .map(u32::to_string)
+            .collect();
+
+        format!(
+            "played {}\nwon {}\ncurrent_streak {}\nmax_streak {}\ndistribution {}\n",
+            self.played,
+            self.won,
+            self.current_streak,
+            self.max_streak,
+            distribution.join(" ")
+        )
+    }
+
+    /// Reads the stats back from what [Stats::serialize]
+    /// wrote; `None` if any line makes no sense.
+    fn parse(contents: &str) -> Option<Stats> {
+        let mut stats = Stats::default();
+
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "played" => stats.played = value.trim().parse().ok()?,
+                "won" => stats.won = value.trim().parse().ok()?,
+                "current_streak" => stats.current_streak = value.trim().parse().ok()?,
+                "max_streak" => stats.max_streak = value.trim().parse().ok()?,
+                "distribution" => stats.distribution = value.split_whitespace()
+                    .map(|count| count.parse().ok())
+                    .collect::<Option<Vec<u32>>>()?,
+                _ => return None
+            }
+        }
+
+        Some(stats)
+    }
+
+}
+
+impl Display for Stats {
+
+    /// Prints the totals followed by the guess
+    /// distribution as a bar chart.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Played {}  Win % {}  Streak {}  Max streak {}",
+            self.played,
+            self.win_rate(),
+            self.current_streak,
+            self.max_streak
+        )?;
+
+        let most = self.distribution.iter()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        for (ind, count) in self.distribution.iter().enumerate() {
+            let bar = "█".repeat((count * BAR_WIDTH / most) as usize);
+            writeln!(f, "{} | {} {}", ind + 1, bar, count)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Where the stats are kept: `wordle/stats` in
+/// `$XDG_CONFIG_HOME`, or else in `$HOME/.config`.
+/// `None` if neither variable is set.
+pub fn stats_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join(STATS_FILE))
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+    use crate::game::{GameLost, Outcome, Word};
+    use crate::stats::Stats;
+
+    /// A game won on guess `attempts`
+    /// to feed into [Stats::record].
+    fn won_in(attempts: u32) -> Outcome {
+        Outcome { attempts, guesses: Vec::new(), lost: None, elapsed: Duration::ZERO }
+    }
+
+    /// A lost game to feed into [Stats::record].
+    fn lost() -> Outcome {
+        Outcome {
+            attempts: 6,
+            guesses: Vec::new(),
+            lost: Some(GameLost::with_word(&Word::analyze_str("bathe"), true)),
+            elapsed: Duration::ZERO
+        }
+    }
+
+    #[test]
+    fn record_win_increments_streak() {
+        let mut stats = Stats::default();
+        stats.record(&won_in(3));
+        stats.record(&won_in(4));
+
+        assert_eq!(2, stats.played);
+        assert_eq!(2, stats.won);
+        assert_eq!(2, stats.current_streak);
+        assert_eq!(2, stats.max_streak);
+    }
+
+    #[test]
+    fn record_loss_resets_streak() {
+        let mut stats = Stats::default();
+        stats.record(&won_in(3));
+        stats.record(&won_in(2));
+        stats.record(&lost());
+
+        assert_eq!(3, stats.played);
+        assert_eq!(2, stats.won);
+        assert_eq!(0, stats.current_streak);
+        assert_eq!(2, stats.max_streak);
+        assert_eq!(66, stats.win_rate());
+    }
+
+    #[test]
+    fn record_buckets_by_winning_guess() {
+        let mut stats = Stats::default();
+        stats.record(&won_in(3));
+        stats.record(&won_in(3));
+        stats.record(&won_in(1));
+        stats.record(&lost());
+
+        assert_eq!(vec![1, 0, 2], stats.distribution);
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        let stats = Stats {
+            played: 7,
+            won: 5,
+            current_streak: 1,
+            max_streak: 3,
+            distribution: vec![0, 1, 3, 1]
+        };
+
+        assert_eq!(Some(&stats), Stats::parse(&stats.serialize()).as_ref());
+    }
+
+    #[test]
+    fn parse_rejects_garbage() {
+        assert_eq!(None, Stats::parse("played lots\n"));
+        assert_eq!(None, Stats::parse("wins 3\n"));
+    }
+
+    #[test]
+    fn display_lists_distribution() {
+        let mut stats = Stats::default();
+        stats.record(&won_in(2));
+        let printed = stats.to_string();
+
+        assert!(printed.contains("Played 1  Win % 100"));
+        assert!(printed.contains("2 | ████████████████████ 1"));
+        assert!(printed.contains("1 |  0"));
+    }
+}